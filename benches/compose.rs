@@ -0,0 +1,60 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use functional_rs::{f, ComposableFn, Compose};
+
+fn step(x: u64) -> u64 {
+    x.wrapping_mul(31).wrapping_add(7)
+}
+
+fn bench_ten_stages(c: &mut Criterion) {
+    let boxed = f!(step)
+        >> f!(step)
+        >> f!(step)
+        >> f!(step)
+        >> f!(step)
+        >> f!(step)
+        >> f!(step)
+        >> f!(step)
+        >> f!(step)
+        >> f!(step);
+    let fused = Compose(step, step) >> step >> step >> step >> step >> step >> step >> step >> step;
+    let erased =
+        (Compose(step, step) >> step >> step >> step >> step >> step >> step >> step >> step)
+            .boxed();
+
+    let mut group = c.benchmark_group("ten_stages");
+    group.bench_function("ComposableFn", |b| b.iter(|| boxed(black_box(1))));
+    group.bench_function("Compose", |b| b.iter(|| fused.apply(black_box(1))));
+    group.bench_function("Compose::boxed", |b| b.iter(|| erased(black_box(1))));
+    group.finish();
+}
+
+fn bench_build(c: &mut Criterion) {
+    let mut group = c.benchmark_group("build_ten_stages");
+    group.bench_function("ComposableFn", |b| {
+        b.iter(|| {
+            black_box(
+                f!(step)
+                    >> f!(step)
+                    >> f!(step)
+                    >> f!(step)
+                    >> f!(step)
+                    >> f!(step)
+                    >> f!(step)
+                    >> f!(step)
+                    >> f!(step)
+                    >> f!(step),
+            )
+        })
+    });
+    group.bench_function("Compose", |b| {
+        b.iter(|| {
+            black_box(
+                Compose(step, step) >> step >> step >> step >> step >> step >> step >> step >> step,
+            )
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_ten_stages, bench_build);
+criterion_main!(benches);
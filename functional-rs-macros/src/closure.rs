@@ -0,0 +1,157 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::visit_mut::{self, VisitMut};
+use syn::{Error, Expr, Ident, Pat, PatIdent, Result, Token, Type};
+
+/// The closure passed to `c!`: `|arg, arg: ty, ..| -> ret body`.
+pub struct CurriedClosure {
+    args: Vec<Arg>,
+    output: Option<Type>,
+    body: Expr,
+}
+
+struct Arg {
+    pat: Pat,
+    ty: Option<Type>,
+}
+
+impl Parse for CurriedClosure {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![async]) {
+            return Err(input.error("c! does not support async closures"));
+        }
+        // Every curried level is a `move` closure already.
+        input.parse::<Option<Token![move]>>()?;
+        if input.peek(Token![||]) {
+            return Err(input.error("c! needs a closure with at least one argument"));
+        }
+        if !input.peek(Token![|]) {
+            return Err(input.error("c! expects a closure, as in `c!(|a, b| a + b)`"));
+        }
+        input.parse::<Token![|]>()?;
+
+        let mut args = Vec::new();
+        while !input.peek(Token![|]) {
+            let pat = Pat::parse_single(input)?;
+            let ty = if input.peek(Token![:]) {
+                input.parse::<Token![:]>()?;
+                Some(input.parse()?)
+            } else {
+                None
+            };
+            args.push(Arg { pat, ty });
+            if input.peek(Token![|]) {
+                break;
+            }
+            if !input.peek(Token![,]) {
+                return Err(input.error("expected `,` or `|` after a closure argument"));
+            }
+            input.parse::<Token![,]>()?;
+        }
+        let closing = input.parse::<Token![|]>()?;
+        if args.is_empty() {
+            return Err(Error::new(
+                closing.span,
+                "c! needs a closure with at least one argument",
+            ));
+        }
+
+        let output = if input.peek(Token![->]) {
+            input.parse::<Token![->]>()?;
+            Some(Type::without_plus(input)?)
+        } else {
+            None
+        };
+        if input.is_empty() {
+            return Err(input.error("expected the closure body"));
+        }
+        let body = input.parse()?;
+        if !input.is_empty() {
+            return Err(input.error("unexpected tokens after the closure body"));
+        }
+
+        Ok(CurriedClosure { args, output, body })
+    }
+}
+
+pub fn expand(closure: CurriedClosure) -> TokenStream {
+    let CurriedClosure {
+        mut args,
+        output,
+        body,
+    } = closure;
+
+    // Every level can be called any number of times, so each one clones the
+    // arguments captured from the outer levels instead of moving them into
+    // the closure it returns. A `mut` binding is only bound mutably by the
+    // innermost closure, so no call sees the changes made by another.
+    let last = args.len() - 1;
+    let captured: Vec<Vec<(Ident, bool)>> = args[..last]
+        .iter_mut()
+        .map(|arg| {
+            let mut bindings = Bindings::default();
+            bindings.visit_pat_mut(&mut arg.pat);
+            bindings.idents
+        })
+        .collect();
+    let clones = |level: usize| {
+        let innermost = level == last;
+        captured[..level]
+            .iter()
+            .flatten()
+            .map(move |(ident, mutable)| {
+                let mutability = (innermost && *mutable).then(|| quote!(mut));
+                quote!(let #mutability #ident = ::core::clone::Clone::clone(&#ident);)
+            })
+    };
+    let output = output.map(|ty| quote!(-> #ty));
+    let body = match body {
+        Expr::Block(block) if block.attrs.is_empty() && block.label.is_none() => {
+            let stmts = block.block.stmts;
+            quote!(#(#stmts)*)
+        }
+        body => quote!(#body),
+    };
+    let innermost_clones = clones(last);
+    let mut expanded = quote! {
+        #output {
+            #(#innermost_clones)*
+            #body
+        }
+    };
+
+    for (level, arg) in args.iter().enumerate().rev() {
+        let pat = &arg.pat;
+        let param = match &arg.ty {
+            Some(ty) => quote!(#pat: #ty),
+            None => quote!(#pat),
+        };
+        expanded = quote!(move |#param| #expanded);
+        if level > 0 {
+            let level_clones = clones(level - 1);
+            expanded = quote!({
+                #(#level_clones)*
+                #expanded
+            });
+        }
+    }
+    expanded
+}
+
+/// Removes `mut` from the bindings of a pattern, collecting their names and
+/// whether they were `mut`.
+#[derive(Default)]
+struct Bindings {
+    idents: Vec<(Ident, bool)>,
+}
+
+impl VisitMut for Bindings {
+    fn visit_pat_ident_mut(&mut self, pat: &mut PatIdent) {
+        if pat.by_ref.is_none() {
+            let mutable = pat.mutability.take().is_some();
+            self.idents.push((pat.ident.clone(), mutable));
+        }
+        visit_mut::visit_pat_ident_mut(self, pat);
+    }
+}
@@ -0,0 +1,413 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::visit_mut::{self, VisitMut};
+use syn::{
+    parse_quote, Error, FnArg, GenericParam, Ident, ItemFn, Lifetime, LifetimeParam,
+    ParenthesizedGenericArguments, Result, ReturnType, Type, TypeBareFn, TypeParam, TypeReference,
+};
+
+pub fn expand(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    let associated = if attr.is_empty() {
+        false
+    } else if syn::parse2::<syn::Token![Self]>(attr.clone()).is_ok() {
+        true
+    } else {
+        return Err(Error::new_spanned(
+            attr,
+            "#[curry] only takes `Self`, as in `#[curry(Self)]` for associated functions",
+        ));
+    };
+    let original: ItemFn = syn::parse2(item)?;
+    let curried = curried_fn(&original, associated)?;
+
+    Ok(quote! {
+        #original
+        #curried
+    })
+}
+
+/// Generates the curried function. With `associated`, it calls the original
+/// function as `Self::name`, for use inside an `impl` block.
+fn curried_fn(original: &ItemFn, associated: bool) -> Result<TokenStream> {
+    let sig = &original.sig;
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(Error::new_spanned(
+            asyncness,
+            "#[curry] does not support async functions",
+        ));
+    }
+    if let Some(unsafety) = &sig.unsafety {
+        return Err(Error::new_spanned(
+            unsafety,
+            "#[curry] does not support unsafe functions",
+        ));
+    }
+    if let Some(variadic) = &sig.variadic {
+        return Err(Error::new_spanned(
+            variadic,
+            "#[curry] does not support variadic functions",
+        ));
+    }
+
+    let mut generics = sig.generics.clone();
+    let mut synthetic = SyntheticParams::default();
+    let mut arg_types = Vec::new();
+    for input in &sig.inputs {
+        match input {
+            FnArg::Receiver(receiver) => {
+                return Err(Error::new_spanned(
+                    receiver,
+                    "#[curry] does not support methods with a `self` receiver",
+                ));
+            }
+            FnArg::Typed(arg) => {
+                let mut ty = (*arg.ty).clone();
+                synthetic.visit_type_mut(&mut ty);
+                arg_types.push(ty);
+            }
+        }
+    }
+    // A free function cannot name `Self`, so a function that does is an
+    // associated one, whose curried version must call `Self::name`.
+    if !associated {
+        let mut self_type = SelfType::default();
+        self_type.visit_item_fn(original);
+        if self_type.mentioned {
+            return Err(Error::new_spanned(
+                &sig.ident,
+                "#[curry] on an associated function needs `#[curry(Self)]`",
+            ));
+        }
+    }
+    if arg_types.len() < 2 {
+        return Err(Error::new_spanned(
+            &sig.ident,
+            "#[curry] needs a function with at least two arguments",
+        ));
+    }
+
+    let mut output = match &sig.output {
+        ReturnType::Default => parse_quote!(()),
+        ReturnType::Type(_, ty) => (**ty).clone(),
+    };
+    let input_lifetimes = input_lifetimes(&arg_types);
+    let mut output_lifetimes = OutputLifetimes {
+        replacement: match input_lifetimes.as_slice() {
+            [lifetime] => Some(lifetime.clone()),
+            _ => None,
+        },
+        error: None,
+    };
+    output_lifetimes.visit_type_mut(&mut output);
+    if let Some(error) = output_lifetimes.error {
+        return Err(error);
+    }
+
+    let lifetime_count = generics.lifetimes().count();
+    for (i, lifetime) in synthetic.lifetimes.iter().enumerate() {
+        generics.params.insert(
+            lifetime_count + i,
+            GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())),
+        );
+    }
+    for (ident, bounds) in &synthetic.types {
+        generics.params.push(GenericParam::Type(TypeParam {
+            attrs: Vec::new(),
+            ident: ident.clone(),
+            colon_token: Some(Default::default()),
+            bounds: bounds.clone(),
+            eq_token: None,
+            default: None,
+        }));
+    }
+
+    let curry_lifetime = Lifetime::new("'__curry", Span::call_site());
+    let nested = arg_types.len() > 2;
+    let generic_idents: Vec<Ident> = generics.type_params().map(|t| t.ident.clone()).collect();
+    let where_clause = generics.make_where_clause();
+    // Bounds on types without generic parameters would be checked where they
+    // are written, so those are left to the `clone` calls below.
+    for ty in &arg_types[..arg_types.len() - 1] {
+        if mentions_generics(ty, &generic_idents) {
+            where_clause.predicates.push(parse_quote_spanned_clone(ty));
+        }
+    }
+    let lifetimes: Vec<Lifetime> = generics.lifetimes().map(|l| l.lifetime.clone()).collect();
+    let type_params: Vec<Ident> = generics.type_params().map(|t| t.ident.clone()).collect();
+    let const_params: Vec<Ident> = generics.const_params().map(|c| c.ident.clone()).collect();
+    if nested {
+        let where_clause = generics.make_where_clause();
+        for lifetime in &lifetimes {
+            where_clause
+                .predicates
+                .push(parse_quote!(#lifetime: #curry_lifetime));
+        }
+        for ident in &type_params {
+            where_clause
+                .predicates
+                .push(parse_quote!(#ident: #curry_lifetime));
+        }
+        generics.params.insert(
+            lifetimes.len(),
+            GenericParam::Lifetime(LifetimeParam::new(curry_lifetime.clone())),
+        );
+    }
+
+    let mut captured_lifetimes = lifetimes.clone();
+    if nested {
+        captured_lifetimes.push(curry_lifetime.clone());
+    }
+    // `use<..>` has to list the type parameters of the surrounding `impl`
+    // block, which the attribute cannot see. Inside one, type parameters are
+    // captured implicitly and lifetimes through the `Captures` trait instead.
+    let captures = if associated {
+        quote!(#(+ ::functional_rs::__private::Captures<#captured_lifetimes>)*)
+    } else {
+        let mut captures: Vec<TokenStream> =
+            captured_lifetimes.iter().map(|l| quote!(#l)).collect();
+        captures.extend(type_params.iter().map(|t| quote!(#t)));
+        captures.extend(const_params.iter().map(|c| quote!(#c)));
+        quote!(+ use<#(#captures),*>)
+    };
+
+    let mut inner_type = output;
+    for ty in arg_types[2..].iter().rev() {
+        inner_type = parse_quote!(::functional_rs::ComposableFn<#curry_lifetime, #ty, #inner_type>);
+    }
+    let second_type = &arg_types[1];
+    let return_type = quote!(impl ::core::ops::Fn(#second_type) -> #inner_type #captures);
+
+    let args: Vec<Ident> = (0..arg_types.len())
+        .map(|i| format_ident!("__arg{}", i))
+        .collect();
+    let original_ident = &sig.ident;
+    let turbofish = explicit_generics(&sig.generics);
+    let path = if associated {
+        quote!(Self::#original_ident)
+    } else {
+        quote!(#original_ident)
+    };
+    let mut body = quote!(#path #turbofish(#(#args),*));
+    for i in (1..arg_types.len()).rev() {
+        let arg = &args[i];
+        let ty = &arg_types[i];
+        // Spanned at the argument type, so a missing `Clone` points there.
+        let clones = args[..i].iter().zip(&arg_types).map(|(captured, ty)| {
+            let spanned = Ident::new(&captured.to_string(), ty.span());
+            quote_spanned!(ty.span()=> let #captured = ::core::clone::Clone::clone(&#spanned);)
+        });
+        let closure = quote! {
+            move |#arg: #ty| {
+                #(#clones)*
+                #body
+            }
+        };
+        body = if i == 1 {
+            closure
+        } else {
+            quote!(::functional_rs::ComposableFn(::std::boxed::Box::new(#closure)))
+        };
+    }
+
+    let vis = &original.vis;
+    let ident = format_ident!("{}_c", original_ident);
+    let doc = format!("Curried form of [`{}`].", original_ident);
+    let first_arg = &args[0];
+    let first_type = &arg_types[0];
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #[doc = #doc]
+        #vis fn #ident #impl_generics(#first_arg: #first_type) -> #return_type #where_clause {
+            #body
+        }
+    })
+}
+
+fn parse_quote_spanned_clone(ty: &Type) -> syn::WherePredicate {
+    syn::parse2(quote_spanned!(ty.span()=> #ty: ::core::clone::Clone))
+        .expect("a type followed by a trait bound is a valid where predicate")
+}
+
+/// Whether `ty` names a lifetime or one of the type parameters in `idents`.
+pub(crate) fn mentions_generics(ty: &Type, idents: &[Ident]) -> bool {
+    let mut used = LifetimeCollector::default();
+    used.visit_type(ty);
+    if !used.lifetimes.is_empty() {
+        return true;
+    }
+    let mut paths = PathIdents::default();
+    paths.visit_type(ty);
+    paths.idents.iter().any(|ident| idents.contains(ident))
+}
+
+/// Returns the turbofish passing the original function's type and const
+/// parameters through, or nothing if it has none.
+fn explicit_generics(generics: &syn::Generics) -> TokenStream {
+    let params: Vec<&Ident> = generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(t) => Some(&t.ident),
+            GenericParam::Const(c) => Some(&c.ident),
+            GenericParam::Lifetime(_) => None,
+        })
+        .collect();
+    if params.is_empty() {
+        TokenStream::new()
+    } else {
+        quote!(::<#(#params),*>)
+    }
+}
+
+/// The distinct lifetimes used by the arguments, after elided ones are named.
+fn input_lifetimes(arg_types: &[Type]) -> Vec<Lifetime> {
+    let mut used = LifetimeCollector::default();
+    for ty in arg_types {
+        used.visit_type(ty);
+    }
+    used.lifetimes
+}
+
+/// Names the elided lifetimes and replaces the `impl Trait` types of the
+/// arguments, which cannot appear in the return type of the curried function.
+#[derive(Default)]
+struct SyntheticParams {
+    lifetimes: Vec<Lifetime>,
+    types: Vec<(
+        Ident,
+        syn::punctuated::Punctuated<syn::TypeParamBound, syn::Token![+]>,
+    )>,
+}
+
+impl SyntheticParams {
+    fn next_lifetime(&mut self, span: Span) -> Lifetime {
+        let lifetime = Lifetime::new(&format!("'__elided{}", self.lifetimes.len()), span);
+        self.lifetimes.push(lifetime.clone());
+        lifetime
+    }
+}
+
+impl VisitMut for SyntheticParams {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        if let Type::ImplTrait(impl_trait) = ty {
+            let ident = format_ident!("__Impl{}", self.types.len());
+            let mut bounds = impl_trait.bounds.clone();
+            for bound in &mut bounds {
+                self.visit_type_param_bound_mut(bound);
+            }
+            self.types.push((ident.clone(), bounds));
+            *ty = parse_quote!(#ident);
+            return;
+        }
+        visit_mut::visit_type_mut(self, ty);
+    }
+
+    fn visit_type_reference_mut(&mut self, reference: &mut TypeReference) {
+        if reference.lifetime.is_none() {
+            reference.lifetime = Some(self.next_lifetime(reference.and_token.span));
+        }
+        visit_mut::visit_type_reference_mut(self, reference);
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime.ident == "_" {
+            *lifetime = self.next_lifetime(lifetime.span());
+        }
+    }
+
+    // Elided lifetimes in function types belong to those types, not to the
+    // curried function.
+    fn visit_type_bare_fn_mut(&mut self, _: &mut TypeBareFn) {}
+
+    fn visit_parenthesized_generic_arguments_mut(&mut self, _: &mut ParenthesizedGenericArguments) {
+    }
+}
+
+#[derive(Default)]
+struct LifetimeCollector {
+    lifetimes: Vec<Lifetime>,
+}
+
+impl Visit<'_> for LifetimeCollector {
+    fn visit_lifetime(&mut self, lifetime: &Lifetime) {
+        if !self.lifetimes.contains(lifetime) {
+            self.lifetimes.push(lifetime.clone());
+        }
+    }
+}
+
+#[derive(Default)]
+struct PathIdents {
+    idents: Vec<Ident>,
+}
+
+impl Visit<'_> for PathIdents {
+    fn visit_path_segment(&mut self, segment: &syn::PathSegment) {
+        self.idents.push(segment.ident.clone());
+        syn::visit::visit_path_segment(self, segment);
+    }
+}
+
+/// Whether a function mentions `Self`, outside of the items nested in it.
+#[derive(Default)]
+struct SelfType {
+    mentioned: bool,
+}
+
+impl Visit<'_> for SelfType {
+    fn visit_path_segment(&mut self, segment: &syn::PathSegment) {
+        self.mentioned |= segment.ident == "Self";
+        syn::visit::visit_path_segment(self, segment);
+    }
+
+    fn visit_item(&mut self, _: &syn::Item) {}
+}
+
+/// Replaces the elided lifetimes of the return type with the only input
+/// lifetime, following the usual elision rules.
+struct OutputLifetimes {
+    replacement: Option<Lifetime>,
+    error: Option<Error>,
+}
+
+impl OutputLifetimes {
+    fn replace(&mut self, span: Span) -> Option<Lifetime> {
+        match &self.replacement {
+            Some(lifetime) => Some(lifetime.clone()),
+            None => {
+                self.error.get_or_insert_with(|| {
+                    Error::new(
+                        span,
+                        "#[curry] cannot infer the lifetime of this reference, name it explicitly",
+                    )
+                });
+                None
+            }
+        }
+    }
+}
+
+impl VisitMut for OutputLifetimes {
+    fn visit_type_reference_mut(&mut self, reference: &mut TypeReference) {
+        if reference.lifetime.is_none() {
+            reference.lifetime = self.replace(reference.and_token.span);
+        }
+        visit_mut::visit_type_reference_mut(self, reference);
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime.ident == "_" {
+            if let Some(replacement) = self.replace(lifetime.span()) {
+                *lifetime = replacement;
+            }
+        }
+    }
+
+    fn visit_type_bare_fn_mut(&mut self, _: &mut TypeBareFn) {}
+
+    fn visit_parenthesized_generic_arguments_mut(&mut self, _: &mut ParenthesizedGenericArguments) {
+    }
+}
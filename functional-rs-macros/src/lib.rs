@@ -0,0 +1,146 @@
+//! Procedural macros for `functional_rs`. Use them through the re-exports in
+//! the `functional_rs` crate.
+
+use proc_macro::TokenStream;
+
+mod closure;
+mod curry;
+mod monoid;
+
+/// This macro curries a closure, allowing partial application of arguments.
+/// `c!(|a, b, c| body)` becomes `move |a| move |b| move |c| body`.
+///
+/// ### Example
+/// The `curry` macro allows you to create curried versions of functions, enabling partial application of arguments.
+///
+/// ```rust
+/// // Import the `curry` macro
+/// use functional_rs::c;
+///
+/// // Curry a simple function that adds two numbers
+/// let add = c!(|a: i32, b: i32| a + b);
+/// let add_5 = add(5); // Partially apply `5` to the function
+/// assert_eq!(add_5(3), 8); // 5 + 3 = 8
+/// ```
+///
+/// The arguments accept everything a closure does: destructuring patterns,
+/// `mut` bindings and any mix of typed and untyped arguments. A return type
+/// may be followed by a plain expression as well as a block.
+///
+/// ```rust
+/// use functional_rs::c;
+///
+/// let shift = c!(|(x, y): (i32, i32), mut dx, dy: i32| -> (i32, i32) {
+///     dx *= 2;
+///     (x + dx, y + dy)
+/// });
+/// assert_eq!(shift((1, 1))(2)(3), (5, 4));
+///
+/// let average = c!(|a: f64, b: f64| -> f64 (a + b) / 2.0);
+/// assert_eq!(average(1.0)(2.0), 1.5);
+/// ```
+///
+/// Partially applied functions can be called repeatedly: every level clones the
+/// arguments it captured instead of moving them, so all arguments except the
+/// last one must be `Clone`. A `mut` binding of an earlier argument is a fresh
+/// clone in every call of the innermost closure.
+#[proc_macro]
+pub fn c(input: TokenStream) -> TokenStream {
+    let closure = syn::parse_macro_input!(input as closure::CurriedClosure);
+    closure::expand(closure).into()
+}
+
+/// Generates a curried version of a function next to the original one.
+///
+/// For a function `add(a, b, c)` the attribute adds `add_c`, which takes the
+/// first argument and returns a function of the next one, so that
+/// `add_c(a)(b)(c)` is `add(a, b, c)`. The function returned by `add_c` is an
+/// `impl Fn`; deeper levels are `ComposableFn`s, so every partial application
+/// can be used as a pipeline stage.
+///
+/// Every curried level can be called any number of times, so all arguments
+/// except the last one must be `Clone`. Generic parameters, `where` clauses,
+/// references with named or elided lifetimes and `impl Trait` arguments are
+/// supported.
+///
+/// ```rust
+/// use functional_rs::curry;
+///
+/// #[curry]
+/// fn add(a: i32, b: i32) -> i32 {
+///     a + b
+/// }
+///
+/// let add_1 = add_c(1);
+/// assert_eq!(add_1(2), 3);
+/// ```
+///
+/// Associated functions without `self` need `#[curry(Self)]`, so that the
+/// curried version calls `Self::new` instead of a free function `new`. Plain
+/// `#[curry]` reports this for functions that mention `Self`; for others, the
+/// attribute cannot tell that they are in an `impl` block, and the compiler
+/// reports that no function `new` exists.
+///
+/// ```rust
+/// use functional_rs::curry;
+///
+/// struct Point(i32, i32);
+///
+/// impl Point {
+///     #[curry(Self)]
+///     fn new(x: i32, y: i32) -> Self {
+///         Point(x, y)
+///     }
+/// }
+///
+/// let on_x_axis = Point::new_c(0);
+/// assert_eq!(on_x_axis(5).0, 0);
+/// ```
+///
+/// The attribute cannot be used on methods taking `self`, on `async` or
+/// `unsafe` functions, on variadic functions, or on functions with fewer than
+/// two arguments.
+#[proc_macro_attribute]
+pub fn curry(attr: TokenStream, item: TokenStream) -> TokenStream {
+    curry::expand(attr.into(), item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `Semigroup` for a struct by combining each field with the same
+/// field of the other value.
+///
+/// Fields whose type uses the struct's generic parameters add a bound to the
+/// implementation, so `Pair<T>` is a semigroup whenever its fields are.
+#[proc_macro_derive(Semigroup)]
+pub fn derive_semigroup(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    monoid::expand_semigroup(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `Monoid` for a struct whose `empty()` value has every field set
+/// to its own `empty()` value. It is usually derived together with
+/// `Semigroup`.
+///
+/// ```rust
+/// use functional_rs::monoid::Sum;
+/// use functional_rs::{Monoid, Semigroup};
+///
+/// #[derive(Semigroup, Monoid, Debug, PartialEq)]
+/// struct Totals {
+///     count: Sum<u32>,
+///     names: String,
+/// }
+///
+/// let total = Totals { count: Sum(1), names: "a".into() }.combine(Totals::empty());
+/// assert_eq!(total, Totals { count: Sum(1), names: "a".into() });
+/// ```
+#[proc_macro_derive(Monoid)]
+pub fn derive_monoid(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    monoid::expand_monoid(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,110 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{parse_quote, Data, DeriveInput, Error, Fields, Ident, Index, Result};
+
+use crate::curry::mentions_generics;
+
+pub fn expand_semigroup(input: DeriveInput) -> Result<TokenStream> {
+    let fields = struct_fields(&input, "Semigroup")?;
+    let trait_path = quote!(::functional_rs::monoid::Semigroup);
+    let body = construct(fields, |member, ty| {
+        quote_spanned!(ty.span()=>
+            ::functional_rs::monoid::Semigroup::combine(self.#member, other.#member)
+        )
+    });
+    let (impl_generics, ty_generics, where_clause) = bounded_generics(&input, fields, &trait_path);
+    let ident = &input.ident;
+
+    Ok(quote! {
+        impl #impl_generics #trait_path for #ident #ty_generics #where_clause {
+            fn combine(self, other: Self) -> Self {
+                #body
+            }
+        }
+    })
+}
+
+pub fn expand_monoid(input: DeriveInput) -> Result<TokenStream> {
+    let fields = struct_fields(&input, "Monoid")?;
+    let trait_path = quote!(::functional_rs::monoid::Monoid);
+    let body = construct(
+        fields,
+        |_, ty| quote_spanned!(ty.span()=> ::functional_rs::monoid::Monoid::empty()),
+    );
+    let (impl_generics, ty_generics, where_clause) = bounded_generics(&input, fields, &trait_path);
+    let ident = &input.ident;
+
+    Ok(quote! {
+        impl #impl_generics #trait_path for #ident #ty_generics #where_clause {
+            fn empty() -> Self {
+                #body
+            }
+        }
+    })
+}
+
+fn struct_fields<'a>(input: &'a DeriveInput, name: &str) -> Result<&'a Fields> {
+    match &input.data {
+        Data::Struct(data) => Ok(&data.fields),
+        Data::Enum(data) => Err(Error::new_spanned(
+            data.enum_token,
+            format!("#[derive({name})] only supports structs"),
+        )),
+        Data::Union(data) => Err(Error::new_spanned(
+            data.union_token,
+            format!("#[derive({name})] only supports structs"),
+        )),
+    }
+}
+
+/// Builds `Self` with every field set to `value(member, type)`.
+fn construct<F>(fields: &Fields, value: F) -> TokenStream
+where
+    F: Fn(TokenStream, &syn::Type) -> TokenStream,
+{
+    match fields {
+        Fields::Named(named) => {
+            let values = named.named.iter().map(|field| {
+                let ident = &field.ident;
+                let value = value(quote!(#ident), &field.ty);
+                quote!(#ident: #value)
+            });
+            quote!(Self { #(#values),* })
+        }
+        Fields::Unnamed(unnamed) => {
+            let values = unnamed.unnamed.iter().enumerate().map(|(i, field)| {
+                let index = Index::from(i);
+                value(quote!(#index), &field.ty)
+            });
+            quote!(Self(#(#values),*))
+        }
+        Fields::Unit => quote!(Self),
+    }
+}
+
+/// Adds a `Field: Trait` bound for every field type that uses the struct's
+/// generic parameters. Other field types are checked where they are used.
+fn bounded_generics(
+    input: &DeriveInput,
+    fields: &Fields,
+    trait_path: &TokenStream,
+) -> (TokenStream, TokenStream, TokenStream) {
+    let mut generics = input.generics.clone();
+    let generic_idents: Vec<Ident> = generics.type_params().map(|t| t.ident.clone()).collect();
+    for field in fields {
+        let ty = &field.ty;
+        if mentions_generics(ty, &generic_idents) {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(#ty: #trait_path));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    (
+        quote!(#impl_generics),
+        quote!(#ty_generics),
+        quote!(#where_clause),
+    )
+}
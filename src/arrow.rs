@@ -0,0 +1,271 @@
+//! Arrow combinators for branching dataflows.
+//!
+//! Note that `*` and `+` bind tighter, and `&` and `|` looser than `>>`, so
+//! mixed expressions usually need parentheses: `(f & g) >> h` and
+//! `f >> (g * h)`.
+
+use crate::{Apply, ComposableFn, Either};
+
+impl<'a, T, U> ComposableFn<'a, T, U> {
+    /// Lifts the function to act on the first element of a pair, passing the
+    /// second one through unchanged.
+    ///
+    /// ```rust
+    /// use functional_rs::{f, ComposableFn};
+    /// let double_first = f!(|x: i32| x * 2).first::<&str>();
+    /// assert_eq!(double_first((4, "four")), (8, "four"));
+    /// ```
+    pub fn first<V>(self) -> ComposableFn<'a, (T, V), (U, V)>
+    where
+        T: 'a,
+        U: 'a,
+    {
+        ComposableFn(Box::new(move |(x, y)| ((self.0)(x), y)))
+    }
+
+    /// Lifts the function to act on the second element of a pair, passing the
+    /// first one through unchanged.
+    pub fn second<V>(self) -> ComposableFn<'a, (V, T), (V, U)>
+    where
+        T: 'a,
+        U: 'a,
+    {
+        ComposableFn(Box::new(move |(x, y)| (x, (self.0)(y))))
+    }
+}
+
+impl<'a, T, U> ComposableFn<'a, T, U> {
+    /// Lifts the function to act on `Left` values, passing `Right` values
+    /// through unchanged.
+    ///
+    /// ```rust
+    /// use functional_rs::{f, ComposableFn, Either};
+    /// let double_left = f!(|x: i32| x * 2).left::<&str>();
+    /// assert_eq!(double_left(Either::Left(4)), Either::Left(8));
+    /// assert_eq!(double_left(Either::Right("four")), Either::Right("four"));
+    /// ```
+    pub fn left<R>(self) -> ComposableFn<'a, Either<T, R>, Either<U, R>>
+    where
+        T: 'a,
+        U: 'a,
+    {
+        ComposableFn(Box::new(move |x: Either<T, R>| x.map_left(&*self.0)))
+    }
+
+    /// Lifts the function to act on `Right` values, passing `Left` values
+    /// through unchanged.
+    pub fn right<L>(self) -> ComposableFn<'a, Either<L, T>, Either<L, U>>
+    where
+        T: 'a,
+        U: 'a,
+    {
+        ComposableFn(Box::new(move |x: Either<L, T>| x.map_right(&*self.0)))
+    }
+}
+
+impl<'a, T, U, V> ComposableFn<'a, T, (U, V)> {
+    /// Combines the two halves of the pair produced by this function with
+    /// `g`, a function of two arguments.
+    ///
+    /// ```rust
+    /// use functional_rs::{f, ComposableFn};
+    /// let len = f!(|s: &str| s.len());
+    /// let words = |s: &str| s.split_whitespace().count();
+    /// let average_word = (len & words).merge(|len: usize, words: usize| len / words);
+    /// assert_eq!(average_word("ab cd"), 2);
+    /// ```
+    pub fn merge<G>(self, g: G) -> ComposableFn<'a, T, G::Output>
+    where
+        T: 'a,
+        U: 'a,
+        V: 'a,
+        G: Apply<(U, V)> + 'a,
+    {
+        ComposableFn(Box::new(move |x| g.apply((self.0)(x))))
+    }
+}
+
+/// Fanout: `f & g` applies both functions to a clone of the same input and
+/// returns both results as a pair.
+///
+/// ```rust
+/// use functional_rs::{f, ComposableFn};
+/// let stats = f!(|v: Vec<i32>| v.iter().sum::<i32>()) & |v: Vec<i32>| v.len();
+/// assert_eq!(stats(vec![1, 2, 3]), (6, 3));
+/// ```
+impl<'a, T, U, G> std::ops::BitAnd<G> for ComposableFn<'a, T, U>
+where
+    T: Clone + 'a,
+    U: 'a,
+    G: Apply<(T,)> + 'a,
+{
+    type Output = ComposableFn<'a, T, (U, G::Output)>;
+
+    fn bitand(self, rhs: G) -> Self::Output {
+        ComposableFn(Box::new(move |x: T| ((self.0)(x.clone()), rhs.apply((x,)))))
+    }
+}
+
+/// Split: `f * g` applies `f` to the first element of a pair and `g` to the
+/// second one. Unlike with `>>` and `&`, the right-hand side must be a
+/// `ComposableFn` too, since its input type is not known from `f`.
+///
+/// ```rust
+/// use functional_rs::{f, ComposableFn};
+/// let both = f!(|x: i32| x + 1) * f!(|s: &str| s.len());
+/// assert_eq!(both((1, "abc")), (2, 3));
+/// ```
+impl<'a, A, B, C, D> std::ops::Mul<ComposableFn<'a, B, D>> for ComposableFn<'a, A, C>
+where
+    A: 'a,
+    B: 'a,
+    C: 'a,
+    D: 'a,
+{
+    type Output = ComposableFn<'a, (A, B), (C, D)>;
+
+    fn mul(self, rhs: ComposableFn<'a, B, D>) -> Self::Output {
+        ComposableFn(Box::new(move |(a, b)| ((self.0)(a), (rhs.0)(b))))
+    }
+}
+
+/// Choice: `f + g` applies `f` to `Left` values and `g` to `Right` values,
+/// keeping the variant. This is Haskell's `+++`. The right-hand side must be a
+/// `ComposableFn`.
+///
+/// ```rust
+/// use functional_rs::{f, ComposableFn, Either};
+/// let both = f!(|x: i32| x + 1) + f!(|s: &str| s.len());
+/// assert_eq!(both(Either::Left(1)), Either::Left(2));
+/// assert_eq!(both(Either::Right("abc")), Either::Right(3));
+/// ```
+impl<'a, A, B, C, D> std::ops::Add<ComposableFn<'a, B, D>> for ComposableFn<'a, A, C>
+where
+    A: 'a,
+    B: 'a,
+    C: 'a,
+    D: 'a,
+{
+    type Output = ComposableFn<'a, Either<A, B>, Either<C, D>>;
+
+    fn add(self, rhs: ComposableFn<'a, B, D>) -> Self::Output {
+        ComposableFn(Box::new(move |x: Either<A, B>| {
+            x.map_left(&*self.0).map_right(&*rhs.0)
+        }))
+    }
+}
+
+/// Fan-in: `f | g` applies `f` to `Left` values and `g` to `Right` values,
+/// merging both branches into the same output type. This is Haskell's `|||`.
+/// The right-hand side must be a `ComposableFn`.
+///
+/// ```rust
+/// use functional_rs::{f, ComposableFn, Either};
+/// let describe = f!(|x: i32| format!("number {x}")) | f!(|s: &str| format!("text {s}"));
+/// assert_eq!(describe(Either::Left(1)), "number 1");
+/// assert_eq!(describe(Either::Right("a")), "text a");
+/// ```
+impl<'a, A, B, U> std::ops::BitOr<ComposableFn<'a, B, U>> for ComposableFn<'a, A, U>
+where
+    A: 'a,
+    B: 'a,
+    U: 'a,
+{
+    type Output = ComposableFn<'a, Either<A, B>, U>;
+
+    fn bitor(self, rhs: ComposableFn<'a, B, U>) -> Self::Output {
+        ComposableFn(Box::new(move |x: Either<A, B>| x.either(&*self.0, &*rhs.0)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{c, f, ComposableFn, Either};
+
+    #[test]
+    fn test_fanout() {
+        let add = c!(|a: i32, b: i32| a + b);
+        let both = f!(|x: i32| x * 2) & add(1);
+        let three = (f!(|x: i32| x) & i32::abs) & |x: i32| x.signum();
+
+        assert_eq!(both(5), (10, 6));
+        assert_eq!(three(-4), ((-4, 4), -1));
+    }
+
+    #[test]
+    fn test_split() {
+        let parse = f!(|s: &str| s.parse::<i32>().unwrap_or(0));
+        let split = parse * f!(|s: String| s.to_uppercase());
+        let pipeline =
+            f!(|x: i32| (x, x.to_string())) >> (f!(|x: i32| x + 1) * f!(|s: String| s.len()));
+
+        assert_eq!(split(("4", "ab".to_string())), (4, "AB".to_string()));
+        assert_eq!(pipeline(100), (101, 3));
+    }
+
+    #[test]
+    fn test_first_and_second() {
+        let add = c!(|a: i32, b: i32| a + b);
+        let pipeline = f!(add(1)).first::<i32>() >> f!(|x: i32| x * 10).second();
+
+        assert_eq!(pipeline((1, 2)), (2, 20));
+    }
+
+    #[test]
+    fn test_branching_dataflow() {
+        let mean = (f!(|v: Vec<f64>| v.iter().sum::<f64>()) & |v: Vec<f64>| v.len() as f64)
+            .merge(|sum: f64, n: f64| sum / n);
+        let min_max = (f!(|v: Vec<i32>| v.iter().copied().min())
+            & |v: Vec<i32>| v.iter().copied().max())
+            >> (f!(Option::unwrap_or_default) * f!(Option::unwrap_or_default))
+            >> ComposableFn::spread(|min: i32, max: i32| max - min);
+
+        assert_eq!(mean(vec![1.0, 2.0, 6.0]), 3.0);
+        assert_eq!(min_max(vec![3, -1, 7]), 8);
+        assert_eq!(min_max(Vec::new()), 0);
+    }
+
+    #[test]
+    fn test_left_and_right() {
+        let add = c!(|a: i32, b: i32| a + b);
+        let pipeline = f!(add(1)).left::<String>() >> f!(|s: String| s + "!").right();
+
+        assert_eq!(pipeline(Either::Left(1)), Either::Left(2));
+        assert_eq!(
+            pipeline(Either::Right("hi".to_string())),
+            Either::Right("hi!".to_string())
+        );
+    }
+
+    #[test]
+    fn test_choice_routes_valid_and_invalid_records() {
+        let classify = f!(|record: &str| match record.split_once('=') {
+            Some((key, value)) => Either::Right((key.to_string(), value.to_string())),
+            None => Either::Left(record.to_string()),
+        });
+        let invalid = f!(|raw: String| raw.len());
+        let valid = f!(|(key, value): (String, String)| format!("{key}: {value}"));
+        let routed = classify >> (invalid + valid);
+
+        assert_eq!(routed("a=1"), Either::Right("a: 1".to_string()));
+        assert_eq!(routed("broken"), Either::Left(6));
+    }
+
+    #[test]
+    fn test_fan_in_with_result() {
+        let parse = f!(|s: &str| s.parse::<i32>()) >> Either::from;
+        let report =
+            f!(|e: std::num::ParseIntError| e.to_string()) | f!(|n: i32| format!("got {n}"));
+        let pipeline = parse >> report;
+        let back_to_result = f!(|x: i32| if x < 0 {
+            Either::Left("negative")
+        } else {
+            Either::Right(x)
+        }) >> Result::from;
+
+        assert_eq!(pipeline("4"), "got 4");
+        assert_eq!(pipeline(""), "cannot parse integer from empty string");
+        assert_eq!(back_to_result(1), Ok(1));
+        assert_eq!(back_to_result(-1), Err("negative"));
+    }
+}
@@ -0,0 +1,418 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::{Apply, ComposableFn, SyncFn};
+
+/// A boxed future, as returned by an [`AsyncComposableFn`].
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// A boxed future that is `Send`, as returned by an [`AsyncSendFn`].
+pub type SendBoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// This macro creates an `AsyncComposableFn` wrapper for a function returning
+/// a future, such as a closure returning an `async` block or an `async fn`.
+///
+/// ```rust
+/// use functional_rs::f_async;
+/// async fn double(x: i32) -> i32 {
+///     x * 2
+/// }
+/// let pipeline = f_async!(double) >> |x: i32| x + 1;
+/// let future = pipeline(5); // resolves to 11 when awaited
+/// ```
+#[macro_export]
+macro_rules! f_async {
+    ($f:expr) => {
+        $crate::AsyncComposableFn::new($f)
+    };
+}
+
+/// This macro creates an `AsyncSendFn` wrapper, the thread-safe counterpart of
+/// `f_async!`. The function must be `Send` and `Sync` and return a `Send`
+/// future.
+///
+/// ```rust
+/// use functional_rs::f_async_send;
+/// async fn double(x: i32) -> i32 {
+///     x * 2
+/// }
+/// let pipeline = f_async_send!(double) >> |x: i32| x + 1;
+/// let handle = std::thread::spawn(move || pipeline(5));
+/// let future = handle.join().unwrap(); // resolves to 11 when awaited
+/// ```
+#[macro_export]
+macro_rules! f_async_send {
+    ($f:expr) => {
+        $crate::AsyncSendFn::new($f)
+    };
+}
+
+/// `AsyncComposableFn` is the asynchronous counterpart of `ComposableFn`:
+/// calling it returns a single future that runs every stage in order.
+///
+/// `>>` accepts both kinds of stages. A plain function of one argument is a
+/// synchronous stage, applied to the output of the previous one, while
+/// another `AsyncComposableFn` is awaited. Closures returning a future can be
+/// added with [`then`](AsyncComposableFn::then). A synchronous `ComposableFn`
+/// can also be followed by an `AsyncComposableFn` with `>>`.
+///
+/// The futures are not tied to any executor. They are not `Send`, so they run
+/// on the thread that created them; use [`AsyncSendFn`] for futures that can
+/// be spawned on a multi-threaded executor.
+///
+/// ```rust
+/// use functional_rs::{f, f_async, ComposableFn};
+/// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+/// #     let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+/// #     let mut future = std::pin::pin!(future);
+/// #     loop {
+/// #         if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+/// #             return output;
+/// #         }
+/// #     }
+/// # }
+///
+/// async fn fetch_len(url: String) -> usize {
+///     url.len()
+/// }
+///
+/// let pipeline = f!(|host: &str| format!("https://{host}")) >> f_async!(fetch_len) >> |n: usize| n * 2;
+/// let future = pipeline("example.com");
+/// assert_eq!(block_on(future), 38);
+/// ```
+pub struct AsyncComposableFn<'a, T, U>(pub Box<dyn Fn(T) -> BoxFuture<'a, U> + 'a>);
+
+impl<'a, T, U> AsyncComposableFn<'a, T, U> {
+    /// Creates an `AsyncComposableFn` from a function returning a future.
+    pub fn new<F, Fut>(f: F) -> Self
+    where
+        F: Fn(T) -> Fut + 'a,
+        Fut: Future<Output = U> + 'a,
+    {
+        AsyncComposableFn(Box::new(move |x| Box::pin(f(x))))
+    }
+
+    /// Applies the wrapped function to `x`, returning the pipeline's future.
+    pub fn apply(&self, x: T) -> BoxFuture<'a, U> {
+        (self.0)(x)
+    }
+
+    /// Adds an asynchronous stage given as a function returning a future.
+    pub fn then<F, Fut>(self, f: F) -> AsyncComposableFn<'a, T, Fut::Output>
+    where
+        T: 'a,
+        U: 'a,
+        F: Fn(U) -> Fut + 'a,
+        Fut: Future + 'a,
+    {
+        self >> AsyncComposableFn::new(f)
+    }
+}
+
+impl<'a, T, U> std::ops::Deref for AsyncComposableFn<'a, T, U> {
+    type Target = dyn Fn(T) -> BoxFuture<'a, U> + 'a;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl<'a, T, U, G> std::ops::Shr<G> for AsyncComposableFn<'a, T, U>
+where
+    T: 'a,
+    U: 'a,
+    G: Apply<(U,)> + 'a,
+{
+    type Output = AsyncComposableFn<'a, T, G::Output>;
+
+    fn shr(self, rhs: G) -> Self::Output {
+        let rhs = Rc::new(rhs);
+        AsyncComposableFn(Box::new(move |x: T| {
+            let future = (self.0)(x);
+            let rhs = Rc::clone(&rhs);
+            Box::pin(async move { rhs.apply((future.await,)) })
+        }))
+    }
+}
+
+impl<'a, T, U, V> std::ops::Shr<AsyncComposableFn<'a, U, V>> for AsyncComposableFn<'a, T, U>
+where
+    T: 'a,
+    U: 'a,
+    V: 'a,
+{
+    type Output = AsyncComposableFn<'a, T, V>;
+
+    fn shr(self, rhs: AsyncComposableFn<'a, U, V>) -> Self::Output {
+        let rhs = Rc::new(rhs);
+        AsyncComposableFn(Box::new(move |x: T| {
+            let future = (self.0)(x);
+            let rhs = Rc::clone(&rhs);
+            Box::pin(async move { (rhs.0)(future.await).await })
+        }))
+    }
+}
+
+impl<'a, T, U, V> std::ops::Shr<AsyncComposableFn<'a, U, V>> for ComposableFn<'a, T, U>
+where
+    T: 'a,
+    U: 'a,
+    V: 'a,
+{
+    type Output = AsyncComposableFn<'a, T, V>;
+
+    fn shr(self, rhs: AsyncComposableFn<'a, U, V>) -> Self::Output {
+        AsyncComposableFn(Box::new(move |x: T| (rhs.0)((self.0)(x))))
+    }
+}
+
+impl<'a, T, U> From<ComposableFn<'a, T, U>> for AsyncComposableFn<'a, T, U>
+where
+    T: 'a,
+    U: 'a,
+{
+    fn from(f: ComposableFn<'a, T, U>) -> Self {
+        AsyncComposableFn(Box::new(move |x| {
+            let y = (f.0)(x);
+            Box::pin(async move { y })
+        }))
+    }
+}
+
+/// `AsyncSendFn` is an `AsyncComposableFn` whose futures are `Send`, so they can
+/// be spawned on a multi-threaded executor, and whose function is `Send` and
+/// `Sync`, so the pipeline can be shared between threads.
+///
+/// Composing with `>>` only accepts `Send + Sync` stages, and the values passed
+/// between stages must be `Send`. A [`SyncFn`] can be followed by an
+/// `AsyncSendFn` with `>>`. Convert the pipeline into an `AsyncComposableFn` to
+/// add weaker stages.
+pub struct AsyncSendFn<'a, T, U>(pub Box<dyn Fn(T) -> SendBoxFuture<'a, U> + Send + Sync + 'a>);
+
+impl<'a, T, U> AsyncSendFn<'a, T, U> {
+    /// Creates an `AsyncSendFn` from a function returning a `Send` future.
+    pub fn new<F, Fut>(f: F) -> Self
+    where
+        F: Fn(T) -> Fut + Send + Sync + 'a,
+        Fut: Future<Output = U> + Send + 'a,
+    {
+        AsyncSendFn(Box::new(move |x| Box::pin(f(x))))
+    }
+
+    /// Applies the wrapped function to `x`, returning the pipeline's future.
+    pub fn apply(&self, x: T) -> SendBoxFuture<'a, U> {
+        (self.0)(x)
+    }
+
+    /// Adds an asynchronous stage given as a function returning a `Send` future.
+    pub fn then<F, Fut>(self, f: F) -> AsyncSendFn<'a, T, Fut::Output>
+    where
+        T: 'a,
+        U: Send + 'a,
+        F: Fn(U) -> Fut + Send + Sync + 'a,
+        Fut: Future + Send + 'a,
+    {
+        self >> AsyncSendFn::new(f)
+    }
+}
+
+impl<'a, T, U> std::ops::Deref for AsyncSendFn<'a, T, U> {
+    type Target = dyn Fn(T) -> SendBoxFuture<'a, U> + Send + Sync + 'a;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl<'a, T, U, G> std::ops::Shr<G> for AsyncSendFn<'a, T, U>
+where
+    T: 'a,
+    U: Send + 'a,
+    G: Apply<(U,)> + Send + Sync + 'a,
+{
+    type Output = AsyncSendFn<'a, T, G::Output>;
+
+    fn shr(self, rhs: G) -> Self::Output {
+        let rhs = Arc::new(rhs);
+        AsyncSendFn(Box::new(move |x: T| {
+            let future = (self.0)(x);
+            let rhs = Arc::clone(&rhs);
+            Box::pin(async move { rhs.apply((future.await,)) })
+        }))
+    }
+}
+
+impl<'a, T, U, V> std::ops::Shr<AsyncSendFn<'a, U, V>> for AsyncSendFn<'a, T, U>
+where
+    T: 'a,
+    U: Send + 'a,
+    V: 'a,
+{
+    type Output = AsyncSendFn<'a, T, V>;
+
+    fn shr(self, rhs: AsyncSendFn<'a, U, V>) -> Self::Output {
+        let rhs = Arc::new(rhs);
+        AsyncSendFn(Box::new(move |x: T| {
+            let future = (self.0)(x);
+            let rhs = Arc::clone(&rhs);
+            Box::pin(async move { (rhs.0)(future.await).await })
+        }))
+    }
+}
+
+impl<'a, T, U, V> std::ops::Shr<AsyncSendFn<'a, U, V>> for SyncFn<'a, T, U>
+where
+    T: 'a,
+    U: 'a,
+    V: 'a,
+{
+    type Output = AsyncSendFn<'a, T, V>;
+
+    fn shr(self, rhs: AsyncSendFn<'a, U, V>) -> Self::Output {
+        AsyncSendFn(Box::new(move |x: T| (rhs.0)((self.0)(x))))
+    }
+}
+
+impl<'a, T, U> From<SyncFn<'a, T, U>> for AsyncSendFn<'a, T, U>
+where
+    T: 'a,
+    U: Send + 'a,
+{
+    fn from(f: SyncFn<'a, T, U>) -> Self {
+        AsyncSendFn(Box::new(move |x| {
+            let y = (f.0)(x);
+            Box::pin(async move { y })
+        }))
+    }
+}
+
+impl<'a, T, U> From<AsyncSendFn<'a, T, U>> for AsyncComposableFn<'a, T, U>
+where
+    T: 'a,
+    U: 'a,
+{
+    fn from(f: AsyncSendFn<'a, T, U>) -> Self {
+        AsyncComposableFn(Box::new(move |x| (f.0)(x)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{c, f, f_sync};
+    use std::cell::Cell;
+    use std::task::{Context, Poll, Waker};
+    use std::thread;
+
+    /// Polls `future` to completion on the current thread, spinning while it
+    /// is pending.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut context = Context::from_waker(Waker::noop());
+        let mut future = Box::pin(future);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    /// A future that is pending the first time it is polled.
+    struct YieldOnce(bool);
+
+    impl Future for YieldOnce {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                Poll::Ready(())
+            } else {
+                self.0 = true;
+                Poll::Pending
+            }
+        }
+    }
+
+    async fn slow_double(x: i32) -> i32 {
+        YieldOnce(false).await;
+        x * 2
+    }
+
+    #[test]
+    fn test_async_and_sync_stages() {
+        let add = c!(|a: i32, b: i32| a + b);
+        let pipeline =
+            f_async!(slow_double) >> add(1) >> f_async!(slow_double) >> i32::wrapping_neg;
+
+        assert_eq!(block_on(pipeline(5)), -22);
+        assert_eq!(block_on(pipeline.apply(0)), -2);
+    }
+
+    #[test]
+    fn test_then_with_async_closure() {
+        let pipeline = f_async!(|s: String| async move { s.len() }).then(|n: usize| async move {
+            YieldOnce(false).await;
+            n * 10
+        });
+
+        assert_eq!(block_on(pipeline("abc".to_string())), 30);
+    }
+
+    #[test]
+    fn test_sync_pipeline_into_async() {
+        let parse = f!(|s: &str| s.parse::<i32>().unwrap_or(0)) >> f_async!(slow_double);
+        let lifted = AsyncComposableFn::from(f!(|x: i32| x + 1)) >> f_async!(slow_double);
+
+        assert_eq!(block_on(parse("21")), 42);
+        assert_eq!(block_on(lifted(1)), 4);
+    }
+
+    #[test]
+    fn test_stages_run_when_awaited() {
+        let calls = Cell::new(0);
+        let count = |x: i32| {
+            calls.set(calls.get() + 1);
+            x
+        };
+        let pipeline = f_async!(slow_double) >> count;
+
+        let future = pipeline(1);
+        assert_eq!(calls.get(), 0);
+        assert_eq!(block_on(future), 2);
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn test_send_futures_run_on_other_threads() {
+        let add = c!(|a: i32, b: i32| a + b);
+        let pipeline = Arc::new(f_async_send!(slow_double) >> add(1) >> f_async_send!(slow_double));
+        let handles: Vec<_> = (0..3)
+            .map(|i| {
+                let pipeline = Arc::clone(&pipeline);
+                thread::spawn(move || block_on(pipeline(i)))
+            })
+            .collect();
+        let results: Vec<i32> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        let future = pipeline.apply(5);
+
+        assert_eq!(results, vec![2, 6, 10]);
+        assert_eq!(thread::spawn(move || block_on(future)).join().unwrap(), 22);
+    }
+
+    #[test]
+    fn test_send_pipeline_conversions() {
+        let counter = Cell::new(0);
+        let count = |x: usize| {
+            counter.set(counter.get() + 1);
+            x
+        };
+        let len = f_sync!(|s: &str| s.len()) >> f_async_send!(|n: usize| async move { n + 1 });
+        let lifted = AsyncSendFn::from(f_sync!(|x: i32| x + 1)).then(slow_double);
+        let local = AsyncComposableFn::from(len) >> count;
+
+        assert_eq!(block_on(lifted(1)), 4);
+        assert_eq!(block_on(local("abc")), 4);
+        assert_eq!(counter.get(), 1);
+    }
+}
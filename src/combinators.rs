@@ -0,0 +1,195 @@
+//! Generic building blocks for pipelines.
+//!
+//! The unary combinators return a `ComposableFn`, so they can start a
+//! pipeline as well as be used as one of its stages. [`flip`] and [`on`]
+//! return functions of two arguments, which start a pipeline through
+//! [`ComposableFn::spread`].
+
+use crate::{Apply, ComposableFn};
+
+/// The identity function: returns its argument unchanged.
+///
+/// ```rust
+/// use functional_rs::combinators::id;
+/// let same = id() >> |x: i32| x + 1;
+/// assert_eq!(same(1), 2);
+/// ```
+pub fn id<'a, T>() -> ComposableFn<'a, T, T> {
+    ComposableFn(Box::new(|x| x))
+}
+
+/// A function that ignores its argument and always returns a clone of
+/// `value`.
+///
+/// ```rust
+/// use functional_rs::combinators::constant;
+/// use functional_rs::{f, ComposableFn};
+/// let always_zero = f!(|s: &str| s.len()) >> constant(0);
+/// assert_eq!(always_zero("abc"), 0);
+/// ```
+pub fn constant<'a, T, U>(value: T) -> ComposableFn<'a, U, T>
+where
+    T: Clone + 'a,
+{
+    ComposableFn(Box::new(move |_| value.clone()))
+}
+
+/// Swaps the two arguments of `f`: `flip(f)(b, a)` is `f(a, b)`.
+///
+/// ```rust
+/// use functional_rs::combinators::flip;
+/// use functional_rs::ComposableFn;
+/// let sub = |a: i32, b: i32| a - b;
+/// assert_eq!(flip(sub)(1, 10), 9);
+///
+/// let sub_then_double = ComposableFn::spread(flip(sub)) >> |x: i32| x * 2;
+/// assert_eq!(sub_then_double.apply((1, 10)), 18);
+/// ```
+pub fn flip<'a, A, B, U, F>(f: F) -> impl Fn(B, A) -> U + 'a
+where
+    F: Fn(A, B) -> U + 'a,
+{
+    move |b, a| f(a, b)
+}
+
+/// Applies `f` to its argument `n` times. `apply_n(f, 0)` is the identity.
+///
+/// ```rust
+/// use functional_rs::combinators::apply_n;
+/// let times_8 = apply_n(|x: u32| x * 2, 3);
+/// assert_eq!(times_8(5), 40);
+/// ```
+pub fn apply_n<'a, T, F>(f: F, n: usize) -> ComposableFn<'a, T, T>
+where
+    F: Apply<(T,), Output = T> + 'a,
+{
+    ComposableFn(Box::new(move |x| (0..n).fold(x, |x, _| f.apply((x,)))))
+}
+
+/// Applies `f` to its argument until `pred` holds, and returns the first
+/// value that satisfies it. The argument itself is returned if it already
+/// satisfies `pred`.
+///
+/// ```rust
+/// use functional_rs::combinators::until;
+/// let next_power_of_ten = until(|x: &u32| *x >= 1000, |x: u32| x * 10);
+/// assert_eq!(next_power_of_ten(7), 7000);
+/// ```
+pub fn until<'a, T, P, F>(pred: P, f: F) -> ComposableFn<'a, T, T>
+where
+    P: Fn(&T) -> bool + 'a,
+    F: Apply<(T,), Output = T> + 'a,
+{
+    ComposableFn(Box::new(move |mut x| {
+        while !pred(&x) {
+            x = f.apply((x,));
+        }
+        x
+    }))
+}
+
+/// Applies the binary function `g` to the results of `key` on both
+/// arguments: `on(g, key)(a, b)` is `g(key(a), key(b))`. This is Haskell's
+/// `Data.Function.on`.
+///
+/// ```rust
+/// use functional_rs::combinators::on;
+/// let same_length = on(|a: usize, b: usize| a == b, str::len);
+/// assert!(same_length("abc", "xyz"));
+/// assert!(!same_length("abc", "xy"));
+/// ```
+pub fn on<'a, T, K, U, G, F>(g: G, key: F) -> impl Fn(T, T) -> U + 'a
+where
+    G: Fn(K, K) -> U + 'a,
+    F: Fn(T) -> K + 'a,
+{
+    move |a, b| g(key(a), key(b))
+}
+
+/// Calls `f` with a reference to its argument for its side effects, then
+/// returns the argument unchanged. Useful to observe the values flowing
+/// through a pipeline.
+///
+/// ```rust
+/// use functional_rs::combinators::tap;
+/// use functional_rs::{f, ComposableFn};
+/// let pipeline = f!(|x: i32| x + 1) >> tap(|x: &i32| println!("after add: {x}")) >> |x: i32| x * 2;
+/// assert_eq!(pipeline(1), 4);
+/// ```
+pub fn tap<'a, T, F>(f: F) -> ComposableFn<'a, T, T>
+where
+    F: Fn(&T) + 'a,
+{
+    ComposableFn(Box::new(move |x| {
+        f(&x);
+        x
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{c, f};
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_id_and_constant() {
+        let double = |x: i32| x * 2;
+        let pipeline = id() >> double >> constant("done");
+
+        assert_eq!(pipeline(4), "done");
+        assert_eq!(id::<&str>()("x"), "x");
+    }
+
+    #[test]
+    fn test_flip() {
+        let div = |a: f64, b: f64| a / b;
+        let div_by = c!(|b: f64, a: f64| flip(div)(b, a));
+
+        assert_eq!(flip(div)(2.0, 10.0), 5.0);
+        assert_eq!((f!(|x: f64| x + 1.0) >> div_by(2.0))(9.0), 5.0);
+        assert_eq!(ComposableFn::spread(flip(div)).apply((4.0, 2.0)), 0.5);
+    }
+
+    #[test]
+    fn test_apply_n() {
+        let add = c!(|a: i32, b: i32| a + b);
+        let add_9 = apply_n(add(3), 3);
+        let nothing = apply_n(f!(|x: i32| x * 100), 0);
+
+        assert_eq!((add_9 >> apply_n(f!(|x: i32| x * 2), 2))(1), 40);
+        assert_eq!(nothing(7), 7);
+    }
+
+    #[test]
+    fn test_until() {
+        let collatz_steps = until(
+            |&(n, _): &(u64, u32)| n == 1,
+            |(n, steps): (u64, u32)| (if n % 2 == 0 { n / 2 } else { 3 * n + 1 }, steps + 1),
+        );
+
+        assert_eq!(collatz_steps((6, 0)), (1, 8));
+        assert_eq!(collatz_steps((1, 0)), (1, 0));
+    }
+
+    #[test]
+    fn test_on() {
+        let longer = on(|a: usize, b: usize| a.max(b), str::len);
+        let total_len =
+            ComposableFn::spread(on(|a: usize, b: usize| a + b, str::len)) >> |n: usize| n * 2;
+
+        assert_eq!(longer("abc", "de"), 3);
+        assert_eq!(total_len.apply(("abc", "de")), 10);
+    }
+
+    #[test]
+    fn test_tap() {
+        let seen = RefCell::new(Vec::new());
+        let record = |x: &i32| seen.borrow_mut().push(*x);
+        let pipeline = f!(|x: i32| x + 1) >> tap(record) >> f!(|x: i32| x * 10) >> tap(record);
+
+        assert_eq!(pipeline(1), 20);
+        assert_eq!(pipeline(2), 30);
+        assert_eq!(*seen.borrow(), [2, 20, 3, 30]);
+    }
+}
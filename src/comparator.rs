@@ -0,0 +1,364 @@
+//! Comparators: functions ordering two values, built from keys.
+
+use std::cmp::Ordering;
+
+/// `Comparator` is a function wrapper comparing two references, for sorting
+/// by several keys without hand-written `cmp` chains.
+///
+/// A `Comparator` dereferences to the wrapped `dyn Fn(&T, &T) -> Ordering`,
+/// so `&*cmp` can be passed to `sort_by`, `max_by` and similar functions on
+/// stable. With the `nightly` feature it implements the `Fn` traits itself,
+/// and `sort_by(&cmp)` works directly. [`wrap`](Comparator::wrap) orders
+/// values in a `BinaryHeap`.
+///
+/// ```rust
+/// use functional_rs::comparator::comparing;
+///
+/// struct Person {
+///     name: &'static str,
+///     age: u32,
+/// }
+///
+/// let mut people = vec![
+///     Person { name: "ada", age: 36 },
+///     Person { name: "alan", age: 41 },
+///     Person { name: "grace", age: 36 },
+/// ];
+/// let by_age_then_name = comparing(|p: &Person| p.age).reversed().then_comparing(|p: &Person| p.name);
+/// people.sort_by(&*by_age_then_name);
+///
+/// let names: Vec<_> = people.iter().map(|p| p.name).collect();
+/// assert_eq!(names, ["alan", "ada", "grace"]);
+/// ```
+pub struct Comparator<'a, T: ?Sized>(pub Box<CompareFn<'a, T>>);
+
+type CompareFn<'a, T> = dyn Fn(&T, &T) -> Ordering + 'a;
+
+/// Compares values by the key extracted with `key`.
+pub fn comparing<'a, T, K, F>(key: F) -> Comparator<'a, T>
+where
+    T: ?Sized,
+    K: Ord,
+    F: Fn(&T) -> K + 'a,
+{
+    Comparator(Box::new(move |a, b| key(a).cmp(&key(b))))
+}
+
+/// Compares values by the key extracted with `key`, ordering the keys with
+/// `cmp`.
+///
+/// ```rust
+/// use functional_rs::comparator::{comparing_with, natural};
+/// let by_len_descending = comparing_with(|s: &&str| s.len(), natural().reversed());
+/// assert_eq!(["a", "abc", "ab"].into_iter().min_by(&*by_len_descending), Some("abc"));
+/// ```
+pub fn comparing_with<'a, T, K, F>(key: F, cmp: Comparator<'a, K>) -> Comparator<'a, T>
+where
+    T: ?Sized,
+    K: 'a,
+    F: Fn(&T) -> K + 'a,
+{
+    Comparator(Box::new(move |a, b| (cmp.0)(&key(a), &key(b))))
+}
+
+/// Compares values by their `Ord` implementation.
+pub fn natural<'a, T>() -> Comparator<'a, T>
+where
+    T: Ord + ?Sized + 'a,
+{
+    Comparator(Box::new(T::cmp))
+}
+
+impl<'a, T: ?Sized> Comparator<'a, T> {
+    /// Creates a `Comparator` from a comparison function.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&T, &T) -> Ordering + 'a,
+    {
+        Comparator(Box::new(f))
+    }
+
+    /// Compares `a` with `b`.
+    pub fn compare(&self, a: &T, b: &T) -> Ordering {
+        (self.0)(a, b)
+    }
+
+    /// Reverses the order.
+    pub fn reversed(self) -> Self
+    where
+        T: 'a,
+    {
+        Comparator(Box::new(move |a, b| (self.0)(b, a)))
+    }
+
+    /// Breaks ties of this comparator with `other`.
+    pub fn then(self, other: Comparator<'a, T>) -> Self
+    where
+        T: 'a,
+    {
+        Comparator(Box::new(move |a, b| {
+            (self.0)(a, b).then_with(|| (other.0)(a, b))
+        }))
+    }
+
+    /// Breaks ties of this comparator by the key extracted with `key`.
+    pub fn then_comparing<K, F>(self, key: F) -> Self
+    where
+        T: 'a,
+        K: Ord,
+        F: Fn(&T) -> K + 'a,
+    {
+        self.then(comparing(key))
+    }
+
+    /// Wraps `value` so that it is ordered by this comparator, for example in
+    /// a `BinaryHeap`.
+    ///
+    /// ```rust
+    /// use functional_rs::comparator::comparing;
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let by_len = comparing(|s: &&str| s.len());
+    /// let mut heap: BinaryHeap<_> = ["ab", "abcd", "a"].into_iter().map(|s| by_len.wrap(s)).collect();
+    /// assert_eq!(heap.pop().map(|o| o.value), Some("abcd"));
+    /// ```
+    pub fn wrap(&self, value: T) -> Ordered<'_, 'a, T>
+    where
+        T: Sized,
+    {
+        Ordered {
+            value,
+            comparator: self,
+        }
+    }
+}
+
+impl<'a, T: 'a> Comparator<'a, T> {
+    /// Lifts the comparator to `Option` values, ordering `None` before every
+    /// `Some` value.
+    pub fn nulls_first(self) -> Comparator<'a, Option<T>> {
+        self.nulls(Ordering::Less)
+    }
+
+    /// Lifts the comparator to `Option` values, ordering `None` after every
+    /// `Some` value.
+    ///
+    /// ```rust
+    /// use functional_rs::comparator::{comparing_with, natural};
+    /// let by_nickname = comparing_with(|p: &(&str, Option<&str>)| p.1, natural().nulls_last());
+    /// let mut people = [("ada", None), ("alan", Some("turing")), ("grace", Some("amazing"))];
+    /// people.sort_by(&*by_nickname);
+    /// assert_eq!(people.map(|p| p.0), ["grace", "alan", "ada"]);
+    /// ```
+    pub fn nulls_last(self) -> Comparator<'a, Option<T>> {
+        self.nulls(Ordering::Greater)
+    }
+
+    /// Lifts the comparator to `Option` values, ordering `None` as `none`
+    /// compared to `Some` values.
+    fn nulls(self, none: Ordering) -> Comparator<'a, Option<T>> {
+        Comparator(Box::new(move |a, b| match (a, b) {
+            (Some(a), Some(b)) => (self.0)(a, b),
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => none,
+            (Some(_), None) => none.reverse(),
+        }))
+    }
+}
+
+impl<'a, T: ?Sized> std::ops::Deref for Comparator<'a, T> {
+    type Target = CompareFn<'a, T>;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+/// A value ordered by a [`Comparator`], created by [`Comparator::wrap`].
+pub struct Ordered<'c, 'a, T> {
+    pub value: T,
+    comparator: &'c Comparator<'a, T>,
+}
+
+impl<T> PartialEq for Ordered<'_, '_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T> Eq for Ordered<'_, '_, T> {}
+
+impl<T> PartialOrd for Ordered<'_, '_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Ordered<'_, '_, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.comparator.0)(&self.value, &other.value)
+    }
+}
+
+#[cfg(not(feature = "nightly"))]
+impl<'a, 'b, 'c, T: ?Sized> crate::ApplyOnce<(&'b T, &'c T)> for Comparator<'a, T> {
+    type Output = Ordering;
+
+    fn apply_once(self, args: (&'b T, &'c T)) -> Ordering {
+        (self.0)(args.0, args.1)
+    }
+}
+
+#[cfg(not(feature = "nightly"))]
+impl<'a, 'b, 'c, T: ?Sized> crate::ApplyMut<(&'b T, &'c T)> for Comparator<'a, T> {
+    fn apply_mut(&mut self, args: (&'b T, &'c T)) -> Ordering {
+        (self.0)(args.0, args.1)
+    }
+}
+
+#[cfg(not(feature = "nightly"))]
+impl<'a, 'b, 'c, T: ?Sized> crate::Apply<(&'b T, &'c T)> for Comparator<'a, T> {
+    fn apply(&self, args: (&'b T, &'c T)) -> Ordering {
+        (self.0)(args.0, args.1)
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<'a, 'b, 'c, T: ?Sized> Fn<(&'b T, &'c T)> for Comparator<'a, T> {
+    extern "rust-call" fn call(&self, args: (&'b T, &'c T)) -> Ordering {
+        (self.0)(args.0, args.1)
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<'a, 'b, 'c, T: ?Sized> FnMut<(&'b T, &'c T)> for Comparator<'a, T> {
+    extern "rust-call" fn call_mut(&mut self, args: (&'b T, &'c T)) -> Ordering {
+        (self.0)(args.0, args.1)
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<'a, 'b, 'c, T: ?Sized> FnOnce<(&'b T, &'c T)> for Comparator<'a, T> {
+    type Output = Ordering;
+
+    extern "rust-call" fn call_once(self, args: (&'b T, &'c T)) -> Ordering {
+        (self.0)(args.0, args.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BinaryHeap;
+
+    #[derive(Debug, PartialEq)]
+    struct Task {
+        name: &'static str,
+        priority: u8,
+        due: Option<u32>,
+    }
+
+    fn tasks() -> Vec<Task> {
+        vec![
+            Task {
+                name: "write",
+                priority: 1,
+                due: Some(3),
+            },
+            Task {
+                name: "review",
+                priority: 2,
+                due: None,
+            },
+            Task {
+                name: "deploy",
+                priority: 2,
+                due: Some(1),
+            },
+            Task {
+                name: "plan",
+                priority: 1,
+                due: None,
+            },
+        ]
+    }
+
+    fn names(tasks: &[Task]) -> Vec<&'static str> {
+        tasks.iter().map(|t| t.name).collect()
+    }
+
+    #[test]
+    fn test_comparing_then_comparing() {
+        let mut tasks = tasks();
+        tasks.sort_by(&*comparing(|t: &Task| t.priority).then_comparing(|t: &Task| t.name));
+
+        assert_eq!(names(&tasks), ["plan", "write", "deploy", "review"]);
+    }
+
+    #[test]
+    fn test_reversed_and_natural() {
+        let mut tasks = tasks();
+        tasks.sort_by(
+            &*comparing(|t: &Task| t.priority)
+                .reversed()
+                .then(comparing_with(|t: &Task| t.name, natural())),
+        );
+        let mut words = ["b", "c", "a"];
+        words.sort_by(&*natural().reversed());
+
+        assert_eq!(names(&tasks), ["deploy", "review", "plan", "write"]);
+        assert_eq!(words, ["c", "b", "a"]);
+    }
+
+    #[test]
+    fn test_nulls_first_and_last() {
+        let mut first = tasks();
+        first.sort_by(
+            &*comparing_with(|t: &Task| t.due, natural().nulls_first())
+                .then_comparing(|t: &Task| t.name),
+        );
+        let mut last = tasks();
+        last.sort_by(
+            &*comparing_with(|t: &Task| t.due, natural().nulls_last())
+                .then_comparing(|t: &Task| t.name),
+        );
+        let mut descending = tasks();
+        descending.sort_by(&*comparing_with(
+            |t: &Task| t.due,
+            natural().reversed().nulls_last(),
+        ));
+
+        assert_eq!(names(&first), ["plan", "review", "deploy", "write"]);
+        assert_eq!(names(&last), ["deploy", "write", "plan", "review"]);
+        assert_eq!(names(&descending)[..2], ["write", "deploy"]);
+    }
+
+    #[test]
+    fn test_max_by_and_binary_heap() {
+        let by_due = comparing_with(|t: &Task| t.due, natural().nulls_last());
+        let latest = tasks().into_iter().max_by(&*by_due).map(|t| t.name);
+        let by_due_then_name = by_due.then_comparing(|t: &Task| t.name);
+        let mut heap: BinaryHeap<_> = tasks()
+            .into_iter()
+            .map(|t| by_due_then_name.wrap(t))
+            .collect();
+
+        assert_eq!(latest, Some("plan"));
+        assert_eq!(heap.pop().map(|o| o.value.name), Some("review"));
+        assert_eq!(heap.pop().map(|o| o.value.name), Some("plan"));
+        assert_eq!(heap.pop().map(|o| o.value.name), Some("write"));
+        assert_eq!(
+            by_due_then_name.compare(&tasks()[2], &tasks()[0]),
+            Ordering::Less
+        );
+    }
+
+    #[cfg(feature = "nightly")]
+    #[test]
+    fn test_comparator_passed_to_sort_by_directly() {
+        let mut words = vec!["bb", "a", "ccc"];
+        words.sort_by(&comparing(|s: &&str| s.len()).reversed());
+
+        assert_eq!(words, ["ccc", "bb", "a"]);
+    }
+}
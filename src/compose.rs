@@ -0,0 +1,174 @@
+use crate::{Apply, ApplyMut, ApplyOnce, ComposableFn};
+
+/// `Compose` is the statically typed counterpart of [`ComposableFn`]. It applies
+/// its first function and passes the result to the second one.
+///
+/// Composing with `>>` nests the stages into a larger `Compose` type instead of
+/// allocating a new boxed closure, so the whole pipeline is monomorphized and can
+/// be inlined. When the concrete type becomes unwieldy, or has to be stored next
+/// to other pipelines, [`boxed`] erases it into a `ComposableFn`.
+///
+/// A `Compose` is called with [`apply`]. With the `nightly` feature it also
+/// implements the `Fn` traits and can be called directly.
+///
+/// ```rust
+/// use functional_rs::Compose;
+/// let add_one = |x: i32| x + 1;
+/// let double = |x: i32| x * 2;
+/// let pipeline = Compose(add_one, double) >> |x: i32| x - 3;
+/// assert_eq!(pipeline.apply(5), 9); // (5 + 1) * 2 - 3 = 9
+/// ```
+///
+/// [`boxed`]: Compose::boxed
+/// [`apply`]: Compose::apply
+#[derive(Clone, Copy)]
+pub struct Compose<F, G>(pub F, pub G);
+
+impl<F, G> Compose<F, G> {
+    /// Applies the pipeline to `x`.
+    #[inline]
+    pub fn apply<T>(&self, x: T) -> <Self as ApplyOnce<(T,)>>::Output
+    where
+        Self: Apply<(T,)>,
+    {
+        Apply::apply(self, (x,))
+    }
+
+    /// Erases the pipeline into a boxed `ComposableFn`.
+    ///
+    /// ```rust
+    /// use functional_rs::{f, Compose, ComposableFn};
+    /// let len = Compose(str::trim, str::len).boxed();
+    /// let composed = len >> f!(|n: usize| n * 10);
+    /// assert_eq!(composed(" abc "), 30);
+    /// ```
+    pub fn boxed<'a, T>(self) -> ComposableFn<'a, T, <Self as ApplyOnce<(T,)>>::Output>
+    where
+        Self: Apply<(T,)> + 'a,
+    {
+        ComposableFn(Box::new(move |x| Apply::apply(&self, (x,))))
+    }
+}
+
+#[cfg(not(feature = "nightly"))]
+impl<T, F, G> Apply<(T,)> for Compose<F, G>
+where
+    F: Apply<(T,)>,
+    G: Apply<(F::Output,)>,
+{
+    #[inline]
+    fn apply(&self, args: (T,)) -> Self::Output {
+        self.1.apply((self.0.apply(args),))
+    }
+}
+
+#[cfg(not(feature = "nightly"))]
+impl<T, F, G> ApplyMut<(T,)> for Compose<F, G>
+where
+    F: ApplyMut<(T,)>,
+    G: ApplyMut<(F::Output,)>,
+{
+    #[inline]
+    fn apply_mut(&mut self, args: (T,)) -> Self::Output {
+        self.1.apply_mut((self.0.apply_mut(args),))
+    }
+}
+
+#[cfg(not(feature = "nightly"))]
+impl<T, F, G> ApplyOnce<(T,)> for Compose<F, G>
+where
+    F: ApplyOnce<(T,)>,
+    G: ApplyOnce<(F::Output,)>,
+{
+    type Output = G::Output;
+
+    #[inline]
+    fn apply_once(self, args: (T,)) -> Self::Output {
+        self.1.apply_once((self.0.apply_once(args),))
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<T, F, G> Fn<(T,)> for Compose<F, G>
+where
+    F: Apply<(T,)>,
+    G: Apply<(F::Output,)>,
+{
+    #[inline]
+    extern "rust-call" fn call(&self, args: (T,)) -> G::Output {
+        self.1.apply((self.0.apply(args),))
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<T, F, G> FnMut<(T,)> for Compose<F, G>
+where
+    F: ApplyMut<(T,)>,
+    G: ApplyMut<(F::Output,)>,
+{
+    #[inline]
+    extern "rust-call" fn call_mut(&mut self, args: (T,)) -> G::Output {
+        self.1.apply_mut((self.0.apply_mut(args),))
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<T, F, G> FnOnce<(T,)> for Compose<F, G>
+where
+    F: ApplyOnce<(T,)>,
+    G: ApplyOnce<(F::Output,)>,
+{
+    type Output = G::Output;
+
+    #[inline]
+    extern "rust-call" fn call_once(self, args: (T,)) -> G::Output {
+        self.1.apply_once((self.0.apply_once(args),))
+    }
+}
+
+impl<F, G, H> std::ops::Shr<H> for Compose<F, G> {
+    type Output = Compose<Compose<F, G>, H>;
+
+    fn shr(self, rhs: H) -> Self::Output {
+        Compose(self, rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::f;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_compose_matches_boxed() {
+        let from_str = i32::from_str;
+        let parse_or_zero = |result: Result<i32, <i32 as FromStr>::Err>| result.unwrap_or(0);
+        let add_10 = |x: i32| x + 10;
+        let fused = Compose(from_str, parse_or_zero) >> add_10;
+        let boxed = f!(from_str) >> f!(parse_or_zero) >> f!(add_10);
+
+        for input in ["4", "-7", "not a number"] {
+            assert_eq!(fused.apply(input), boxed(input));
+        }
+    }
+
+    #[test]
+    fn test_compose_boxed() {
+        fn first_word(s: &str) -> &str {
+            s.split_whitespace().next().unwrap_or("")
+        }
+        let len = |s: &str| s.len();
+        let boxed = Compose(first_word, len).boxed() >> f!(|n: usize| n * 2);
+
+        assert_eq!(boxed("hello world"), 10);
+    }
+
+    #[test]
+    fn test_compose_nested_stages() {
+        let inner = Compose(|x: u32| x + 1, |x: u32| x * 3);
+        let outer = Compose(inner, Compose(|x: u32| x - 2, |x: u32| x.to_string()));
+
+        assert_eq!(outer.apply(4), "13");
+    }
+}
@@ -0,0 +1,250 @@
+use std::rc::Rc;
+
+use crate::{Apply, ApplyOnce, ComposableFn};
+
+/// Turns a function of several arguments into its curried form at runtime.
+///
+/// `f.curry()` takes the first argument and returns a function of the next
+/// one, so that `f.curry()(a)(b)(c)` is `f(a, b, c)`. Every level is a
+/// `ComposableFn` and can be used as a pipeline stage. This is the runtime
+/// counterpart of [`c!`](crate::c), for function values that already exist.
+///
+/// Every level can be called any number of times, so all arguments except
+/// the last one must be `Clone`.
+///
+/// ```rust
+/// use functional_rs::{f, ComposableFn, Curry};
+/// use std::str::FromStr;
+///
+/// let add = |a: i32, b: i32| a + b;
+/// let parse_or_zero = |result: Result<i32, _>| result.unwrap_or(0);
+/// let add_10_from_str = f!(i32::from_str) >> parse_or_zero >> add.curry()(10);
+/// assert_eq!(add_10_from_str("4"), 14);
+/// ```
+pub trait Curry<'a, Args> {
+    type Curried;
+
+    fn curry(self) -> Self::Curried;
+}
+
+/// The curried type of a function of the given arguments.
+macro_rules! curried {
+    ($a:lifetime, $out:ty; $arg:ident) => {
+        ComposableFn<$a, $arg, $out>
+    };
+    ($a:lifetime, $out:ty; $arg:ident, $($rest:ident),+) => {
+        ComposableFn<$a, $arg, curried!($a, $out; $($rest),+)>
+    };
+}
+
+macro_rules! impl_curry {
+    ($first:ident $first_arg:ident; $last:ident $last_arg:ident) => {
+        impl<'a, $first, $last, U, F> Curry<'a, ($first, $last)> for F
+        where
+            F: Apply<($first, $last), Output = U> + 'a,
+            $first: Clone + 'a,
+        {
+            type Curried = curried!('a, U; $first, $last);
+
+            fn curry(self) -> Self::Curried {
+                let f = Rc::new(self);
+                ComposableFn(Box::new(move |$first_arg: $first| {
+                    let f = Rc::clone(&f);
+                    ComposableFn(Box::new(move |$last_arg: $last| {
+                        f.apply(($first_arg.clone(), $last_arg))
+                    }))
+                }))
+            }
+        }
+    };
+    ($first:ident $first_arg:ident, $($mid:ident $mid_arg:ident),+; $last:ident $last_arg:ident) => {
+        impl<'a, $first, $($mid,)+ $last, U, F> Curry<'a, ($first, $($mid,)+ $last)> for F
+        where
+            F: Apply<($first, $($mid,)+ $last), Output = U> + 'a,
+            $first: Clone + 'a,
+            $($mid: Clone + 'a,)+
+        {
+            type Curried = curried!('a, U; $first, $($mid,)+ $last);
+
+            fn curry(self) -> Self::Curried {
+                let f = Rc::new(self);
+                ComposableFn(Box::new(move |$first_arg: $first| {
+                    let f = Rc::clone(&f);
+                    let rest = move |$($mid_arg: $mid,)+ $last_arg: $last| {
+                        f.apply(($first_arg.clone(), $($mid_arg,)+ $last_arg))
+                    };
+                    rest.curry()
+                }))
+            }
+        }
+    };
+}
+
+impl_curry!(A a; B b);
+impl_curry!(A a, B b; C c);
+impl_curry!(A a, B b, C c; D d);
+impl_curry!(A a, B b, C c, D d; E e);
+impl_curry!(A a, B b, C c, D d, E e; G g);
+
+/// Turns a curried function, such as one created by [`c!`](crate::c) or
+/// [`Curry::curry`], back into a function of several arguments, so it can be
+/// passed to APIs expecting a multi-argument callback.
+///
+/// `f.uncurry3()` is `move |a, b, c| f(a)(b)(c)`. Only the outer level is
+/// called more than once, so the inner levels may be `FnOnce`.
+///
+/// ```rust
+/// use functional_rs::{c, Uncurry};
+///
+/// let add = c!(|a: i32, b: i32| a + b);
+/// let sum = [1, 2, 3].into_iter().fold(0, add.uncurry2());
+/// assert_eq!(sum, 6);
+/// ```
+pub trait Uncurry<A>: Apply<(A,)> + Sized {
+    fn uncurry2<B, U>(self) -> impl Fn(A, B) -> U
+    where
+        Self::Output: ApplyOnce<(B,), Output = U>,
+    {
+        move |a, b| self.apply((a,)).apply_once((b,))
+    }
+
+    fn uncurry3<B, C, G, U>(self) -> impl Fn(A, B, C) -> U
+    where
+        Self::Output: ApplyOnce<(B,), Output = G>,
+        G: ApplyOnce<(C,), Output = U>,
+    {
+        move |a, b, c| self.apply((a,)).apply_once((b,)).apply_once((c,))
+    }
+
+    fn uncurry4<B, C, D, G, H, U>(self) -> impl Fn(A, B, C, D) -> U
+    where
+        Self::Output: ApplyOnce<(B,), Output = G>,
+        G: ApplyOnce<(C,), Output = H>,
+        H: ApplyOnce<(D,), Output = U>,
+    {
+        move |a, b, c, d| {
+            self.apply((a,))
+                .apply_once((b,))
+                .apply_once((c,))
+                .apply_once((d,))
+        }
+    }
+
+    fn uncurry5<B, C, D, E, G, H, I, U>(self) -> impl Fn(A, B, C, D, E) -> U
+    where
+        Self::Output: ApplyOnce<(B,), Output = G>,
+        G: ApplyOnce<(C,), Output = H>,
+        H: ApplyOnce<(D,), Output = I>,
+        I: ApplyOnce<(E,), Output = U>,
+    {
+        move |a, b, c, d, e| {
+            self.apply((a,))
+                .apply_once((b,))
+                .apply_once((c,))
+                .apply_once((d,))
+                .apply_once((e,))
+        }
+    }
+
+    fn uncurry6<B, C, D, E, F, G, H, I, J, U>(self) -> impl Fn(A, B, C, D, E, F) -> U
+    where
+        Self::Output: ApplyOnce<(B,), Output = G>,
+        G: ApplyOnce<(C,), Output = H>,
+        H: ApplyOnce<(D,), Output = I>,
+        I: ApplyOnce<(E,), Output = J>,
+        J: ApplyOnce<(F,), Output = U>,
+    {
+        move |a, b, c, d, e, f| {
+            self.apply((a,))
+                .apply_once((b,))
+                .apply_once((c,))
+                .apply_once((d,))
+                .apply_once((e,))
+                .apply_once((f,))
+        }
+    }
+}
+
+impl<A, F> Uncurry<A> for F where F: Apply<(A,)> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{c, curry, f};
+
+    #[test]
+    fn test_curry_closure() {
+        let add = |a: i32, b: i32| a + b;
+        let add_5 = add.curry()(5);
+
+        assert_eq!(add_5(3), 8);
+        assert_eq!(add_5(4), 9);
+    }
+
+    #[test]
+    fn test_curry_fn_item_as_pipeline_stage() {
+        fn clamp(low: i32, high: i32, x: i32) -> i32 {
+            x.max(low).min(high)
+        }
+
+        let between_0_and_10 = clamp.curry()(0)(10);
+        let pipeline = f!(|x: i32| x * 3) >> between_0_and_10;
+
+        assert_eq!(pipeline(2), 6);
+        assert_eq!(pipeline(5), 10);
+    }
+
+    #[test]
+    fn test_curry_clones_earlier_arguments() {
+        let join = |sep: String, a: &str, b: &str| format!("{a}{sep}{b}");
+        let comma = join.curry()(", ".to_string());
+
+        assert_eq!(comma("a")("b"), "a, b");
+        assert_eq!(comma("c")("d"), "c, d");
+    }
+
+    #[test]
+    fn test_curry_six_arguments() {
+        let sum = |a: u8, b: u8, c: u8, d: u8, e: u8, g: u8| a + b + c + d + e + g;
+
+        assert_eq!(sum.curry()(1)(2)(3)(4)(5)(6), 21);
+    }
+
+    #[test]
+    fn test_uncurry_c_macro() {
+        let add = c!(|a: i32, b: i32| a + b);
+        let volume = c!(|w: i32, h: i32, d: i32| w * h * d);
+        fn zip_with(xs: &[i32], ys: &[i32], f: impl Fn(i32, i32) -> i32) -> Vec<i32> {
+            xs.iter().zip(ys).map(|(&x, &y)| f(x, y)).collect()
+        }
+
+        assert_eq!(zip_with(&[1, 3], &[2, 4], add.uncurry2()), [3, 7]);
+        assert_eq!([2, 3, 4].into_iter().fold(0, add.uncurry2()), 9);
+        assert_eq!(volume.uncurry3()(2, 3, 4), 24);
+    }
+
+    #[test]
+    fn test_uncurry_inverts_curry() {
+        #[curry]
+        fn describe(name: &str, age: u32, city: &str) -> String {
+            format!("{name} ({age}) from {city}")
+        }
+
+        let add = |a: i32, b: i32, c: i32, d: i32| a + b + c + d;
+
+        assert_eq!(
+            describe_c.uncurry3()("ada", 36, "London"),
+            "ada (36) from London"
+        );
+        assert_eq!(add.curry().uncurry4()(1, 2, 3, 4), 10);
+    }
+
+    #[test]
+    fn test_uncurry_once_inner_levels() {
+        let label = |prefix: &'static str| {
+            move |name: String| move |n: usize| format!("{prefix}{}", name.repeat(n))
+        };
+
+        assert_eq!(label.uncurry3()("> ", "ab".to_string(), 2), "> abab");
+    }
+}
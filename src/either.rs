@@ -0,0 +1,133 @@
+/// A value of one of two types.
+///
+/// `Either` is the input and output type of the choice combinators on
+/// `ComposableFn` ([`left`], [`right`], `+` and `|`), which route each variant
+/// through its own branch of a pipeline.
+///
+/// A `Result<T, E>` converts into an `Either<E, T>` and back: by convention
+/// `Left` holds the error and `Right` the successful value.
+///
+/// [`left`]: crate::ComposableFn::left
+/// [`right`]: crate::ComposableFn::right
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+impl<L, R> Either<L, R> {
+    /// Returns `true` if this is a `Left` value.
+    pub fn is_left(&self) -> bool {
+        matches!(self, Either::Left(_))
+    }
+
+    /// Returns `true` if this is a `Right` value.
+    pub fn is_right(&self) -> bool {
+        matches!(self, Either::Right(_))
+    }
+
+    /// Returns the `Left` value, if any.
+    pub fn left(self) -> Option<L> {
+        match self {
+            Either::Left(l) => Some(l),
+            Either::Right(_) => None,
+        }
+    }
+
+    /// Returns the `Right` value, if any.
+    pub fn right(self) -> Option<R> {
+        match self {
+            Either::Left(_) => None,
+            Either::Right(r) => Some(r),
+        }
+    }
+
+    /// Applies `f` to a `Left` value, leaving a `Right` value unchanged.
+    pub fn map_left<L2, F>(self, f: F) -> Either<L2, R>
+    where
+        F: FnOnce(L) -> L2,
+    {
+        match self {
+            Either::Left(l) => Either::Left(f(l)),
+            Either::Right(r) => Either::Right(r),
+        }
+    }
+
+    /// Applies `f` to a `Right` value, leaving a `Left` value unchanged.
+    pub fn map_right<R2, F>(self, f: F) -> Either<L, R2>
+    where
+        F: FnOnce(R) -> R2,
+    {
+        match self {
+            Either::Left(l) => Either::Left(l),
+            Either::Right(r) => Either::Right(f(r)),
+        }
+    }
+
+    /// Reduces both variants to the same type, with `f` for `Left` values and
+    /// `g` for `Right` values.
+    pub fn either<U, F, G>(self, f: F, g: G) -> U
+    where
+        F: FnOnce(L) -> U,
+        G: FnOnce(R) -> U,
+    {
+        match self {
+            Either::Left(l) => f(l),
+            Either::Right(r) => g(r),
+        }
+    }
+
+    /// Swaps the two variants.
+    pub fn flip(self) -> Either<R, L> {
+        match self {
+            Either::Left(l) => Either::Right(l),
+            Either::Right(r) => Either::Left(r),
+        }
+    }
+}
+
+impl<T, E> From<Result<T, E>> for Either<E, T> {
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(t) => Either::Right(t),
+            Err(e) => Either::Left(e),
+        }
+    }
+}
+
+impl<T, E> From<Either<E, T>> for Result<T, E> {
+    fn from(either: Either<E, T>) -> Self {
+        match either {
+            Either::Left(e) => Err(e),
+            Either::Right(t) => Ok(t),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_either_accessors() {
+        let left: Either<i32, &str> = Either::Left(1);
+        let right: Either<i32, &str> = Either::Right("one");
+
+        assert!(left.is_left() && right.is_right());
+        assert_eq!((left.left(), left.right()), (Some(1), None));
+        assert_eq!(right.map_right(str::len), Either::Right(3));
+        assert_eq!(left.map_left(|x| x + 1).flip(), Either::Right(2));
+        assert_eq!(right.either(|x| x as usize, str::len), 3);
+    }
+
+    #[test]
+    fn test_either_result_conversions() {
+        let ok: Result<i32, String> = Ok(1);
+        let err: Result<i32, String> = Err("bad".to_string());
+
+        assert_eq!(Either::from(ok.clone()), Either::Right(1));
+        assert_eq!(Either::from(err.clone()), Either::Left("bad".to_string()));
+        assert_eq!(Result::from(Either::from(ok.clone())), ok);
+        assert_eq!(Result::from(Either::from(err.clone())), err);
+    }
+}
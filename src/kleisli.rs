@@ -0,0 +1,423 @@
+use crate::{Apply, ComposableFn};
+
+/// This macro creates a `TryFn` wrapper for a closure returning a `Result`,
+/// the fallible counterpart of `f!`.
+///
+/// ```rust
+/// use functional_rs::try_f;
+/// let parse = try_f!(|s: &str| s.parse::<i32>());
+/// assert_eq!(parse("4"), Ok(4));
+/// ```
+#[macro_export]
+macro_rules! try_f {
+    ($f:expr) => {
+        $crate::TryFn(Box::new($f))
+    };
+}
+
+/// This macro creates an `OptFn` wrapper for a closure returning an `Option`.
+///
+/// ```rust
+/// use functional_rs::opt_f;
+/// let first_word = opt_f!(|s: &str| s.split_whitespace().next().map(str::len));
+/// assert_eq!(first_word("hello world"), Some(5));
+/// ```
+#[macro_export]
+macro_rules! opt_f {
+    ($f:expr) => {
+        $crate::OptFn(Box::new($f))
+    };
+}
+
+/// `TryFn` is a function wrapper for stages that can fail, returning a
+/// `Result<U, E>`.
+///
+/// Composing with `>>` is Kleisli composition: the next stage takes the `Ok`
+/// value and returns a `Result` itself, and the pipeline stops at the first
+/// `Err`. Errors of later stages are converted into the pipeline's error type
+/// `E` with `From`, like the `?` operator does. Stages that cannot fail are
+/// added with [`map`](TryFn::map).
+///
+/// ```rust
+/// use functional_rs::{try_f, TryFn};
+/// use std::num::ParseIntError;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum AppError {
+///     Parse(ParseIntError),
+///     Negative(i32),
+/// }
+///
+/// impl From<ParseIntError> for AppError {
+///     fn from(e: ParseIntError) -> Self {
+///         AppError::Parse(e)
+///     }
+/// }
+///
+/// let validate = |n: i32| if n < 0 { Err(AppError::Negative(n)) } else { Ok(n) };
+/// let pipeline = TryFn::lift(str::trim) >> str::parse::<i32> >> validate;
+///
+/// assert_eq!(pipeline(" 4 "), Ok(4));
+/// assert_eq!(pipeline("-4"), Err(AppError::Negative(-4)));
+/// assert!(matches!(pipeline("four"), Err(AppError::Parse(_))));
+/// ```
+pub struct TryFn<'a, T, U, E>(pub Box<dyn Fn(T) -> Result<U, E> + 'a>);
+
+impl_unary_fn!(TryFn<U, E> => Result<U, E>);
+
+impl<'a, T, U, E> TryFn<'a, T, U, E> {
+    /// Creates a `TryFn` from a stage that cannot fail.
+    pub fn lift<F>(f: F) -> Self
+    where
+        F: Apply<(T,), Output = U> + 'a,
+    {
+        TryFn(Box::new(move |x| Ok(f.apply((x,)))))
+    }
+
+    /// Adds a stage that cannot fail, applied to the `Ok` value.
+    pub fn map<G>(self, g: G) -> TryFn<'a, T, G::Output, E>
+    where
+        T: 'a,
+        U: 'a,
+        E: 'a,
+        G: Apply<(U,)> + 'a,
+    {
+        TryFn(Box::new(move |x| (self.0)(x).map(|y| g.apply((y,)))))
+    }
+
+    /// Transforms the error of the pipeline with `g`.
+    pub fn map_err<E2, G>(self, g: G) -> TryFn<'a, T, U, E2>
+    where
+        T: 'a,
+        U: 'a,
+        E: 'a,
+        G: Apply<(E,), Output = E2> + 'a,
+    {
+        TryFn(Box::new(move |x| (self.0)(x).map_err(|e| g.apply((e,)))))
+    }
+
+    /// Converts the error of the pipeline into `E2`, so later stages can
+    /// return errors convertible into `E2` rather than into `E`.
+    ///
+    /// ```rust
+    /// use functional_rs::try_f;
+    /// let pipeline = try_f!(|s: &str| s.parse::<i32>())
+    ///     .err_into::<Box<dyn std::error::Error>>()
+    ///     >> |n: i32| u8::try_from(n);
+    /// assert_eq!(pipeline("200").unwrap(), 200);
+    /// assert!(pipeline("300").is_err());
+    /// ```
+    pub fn err_into<E2>(self) -> TryFn<'a, T, U, E2>
+    where
+        T: 'a,
+        U: 'a,
+        E: 'a,
+        E2: From<E>,
+    {
+        TryFn(Box::new(move |x| (self.0)(x).map_err(E2::from)))
+    }
+}
+
+impl<'a, T, U, E, G, V, E2> std::ops::Shr<G> for TryFn<'a, T, U, E>
+where
+    T: 'a,
+    U: 'a,
+    E: From<E2> + 'a,
+    G: Apply<(U,), Output = Result<V, E2>> + 'a,
+{
+    type Output = TryFn<'a, T, V, E>;
+
+    fn shr(self, rhs: G) -> Self::Output {
+        TryFn(Box::new(move |x: T| {
+            let y = (self.0)(x)?;
+            Ok(rhs.apply((y,))?)
+        }))
+    }
+}
+
+impl<'a, T, U, E> TryFn<'a, T, U, E> {
+    /// Discards the error, turning the pipeline into an [`OptFn`].
+    pub fn ok(self) -> OptFn<'a, T, U>
+    where
+        T: 'a,
+        U: 'a,
+        E: 'a,
+    {
+        OptFn(Box::new(move |x| (self.0)(x).ok()))
+    }
+}
+
+impl<'a, T, U, E> From<ComposableFn<'a, T, Result<U, E>>> for TryFn<'a, T, U, E> {
+    fn from(f: ComposableFn<'a, T, Result<U, E>>) -> Self {
+        TryFn(f.0)
+    }
+}
+
+impl<'a, T, U, E> From<TryFn<'a, T, U, E>> for ComposableFn<'a, T, Result<U, E>> {
+    fn from(f: TryFn<'a, T, U, E>) -> Self {
+        ComposableFn(f.0)
+    }
+}
+
+/// `OptFn` is a function wrapper for stages that may not produce a value,
+/// returning an `Option<U>`, such as map lookups.
+///
+/// Composing with `>>` is Kleisli composition: the next stage takes the
+/// `Some` value and returns an `Option` itself, and the pipeline stops at the
+/// first `None`. `|` combines two stages taking the same input, using the
+/// second one when the first returns `None`.
+///
+/// [`ok_or`](OptFn::ok_or) turns an `OptFn` into a [`TryFn`] and
+/// [`TryFn::ok`] goes the other way, so both kinds of stages can be mixed.
+///
+/// ```rust
+/// use functional_rs::opt_f;
+/// use std::collections::HashMap;
+///
+/// let users = HashMap::from([("ada", 1), ("alan", 2)]);
+/// let emails = HashMap::from([(1, "ada@example.com")]);
+///
+/// let email = opt_f!(|name: &str| users.get(name).copied()) >> |id: i32| emails.get(&id).copied();
+/// assert_eq!(email("ada"), Some("ada@example.com"));
+/// assert_eq!(email("alan"), None);
+/// assert_eq!(email("grace"), None);
+/// ```
+pub struct OptFn<'a, T, U>(pub Box<dyn Fn(T) -> Option<U> + 'a>);
+
+impl_unary_fn!(OptFn<U> => Option<U>);
+
+impl<'a, T, U> OptFn<'a, T, U> {
+    /// Creates an `OptFn` from a stage that always produces a value.
+    pub fn lift<F>(f: F) -> Self
+    where
+        F: Apply<(T,), Output = U> + 'a,
+    {
+        OptFn(Box::new(move |x| Some(f.apply((x,)))))
+    }
+
+    /// Adds a stage that always produces a value, applied to the `Some` value.
+    pub fn map<G>(self, g: G) -> OptFn<'a, T, G::Output>
+    where
+        T: 'a,
+        U: 'a,
+        G: Apply<(U,)> + 'a,
+    {
+        OptFn(Box::new(move |x| (self.0)(x).map(|y| g.apply((y,)))))
+    }
+
+    /// Uses `other` on the same input when this pipeline returns `None`.
+    /// `a.or_else(b)` is `a | b`.
+    pub fn or_else<G>(self, other: G) -> OptFn<'a, T, U>
+    where
+        T: Clone + 'a,
+        U: 'a,
+        G: Apply<(T,), Output = Option<U>> + 'a,
+    {
+        OptFn(Box::new(move |x: T| {
+            (self.0)(x.clone()).or_else(|| other.apply((x,)))
+        }))
+    }
+
+    /// Turns the pipeline into a [`TryFn`] failing with a clone of `err`
+    /// when it returns `None`.
+    ///
+    /// ```rust
+    /// use functional_rs::opt_f;
+    /// let first_char = opt_f!(|s: &str| s.chars().next()).ok_or("empty");
+    /// let digit = first_char >> |c: char| c.to_digit(10).ok_or("not a digit");
+    /// assert_eq!(digit("7up"), Ok(7));
+    /// assert_eq!(digit(""), Err("empty"));
+    /// assert_eq!(digit("up"), Err("not a digit"));
+    /// ```
+    pub fn ok_or<E>(self, err: E) -> TryFn<'a, T, U, E>
+    where
+        T: 'a,
+        U: 'a,
+        E: Clone + 'a,
+    {
+        TryFn(Box::new(move |x| (self.0)(x).ok_or_else(|| err.clone())))
+    }
+
+    /// Turns the pipeline into a [`TryFn`] failing with the result of `err`
+    /// when it returns `None`.
+    pub fn ok_or_else<E, F>(self, err: F) -> TryFn<'a, T, U, E>
+    where
+        T: 'a,
+        U: 'a,
+        F: Fn() -> E + 'a,
+    {
+        TryFn(Box::new(move |x| (self.0)(x).ok_or_else(&err)))
+    }
+}
+
+impl<'a, T, U, G, V> std::ops::Shr<G> for OptFn<'a, T, U>
+where
+    T: 'a,
+    U: 'a,
+    G: Apply<(U,), Output = Option<V>> + 'a,
+{
+    type Output = OptFn<'a, T, V>;
+
+    fn shr(self, rhs: G) -> Self::Output {
+        OptFn(Box::new(move |x: T| rhs.apply(((self.0)(x)?,))))
+    }
+}
+
+impl<'a, T, U, G> std::ops::BitOr<G> for OptFn<'a, T, U>
+where
+    T: Clone + 'a,
+    U: 'a,
+    G: Apply<(T,), Output = Option<U>> + 'a,
+{
+    type Output = OptFn<'a, T, U>;
+
+    fn bitor(self, rhs: G) -> Self::Output {
+        self.or_else(rhs)
+    }
+}
+
+impl<'a, T, U> From<ComposableFn<'a, T, Option<U>>> for OptFn<'a, T, U> {
+    fn from(f: ComposableFn<'a, T, Option<U>>) -> Self {
+        OptFn(f.0)
+    }
+}
+
+impl<'a, T, U> From<OptFn<'a, T, U>> for ComposableFn<'a, T, Option<U>> {
+    fn from(f: OptFn<'a, T, U>) -> Self {
+        ComposableFn(f.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::f;
+    use std::collections::HashMap;
+    use std::num::ParseIntError;
+    use std::str::FromStr;
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum NumError {
+        Parse(ParseIntError),
+        TooLarge(i32),
+    }
+
+    impl From<ParseIntError> for NumError {
+        fn from(e: ParseIntError) -> Self {
+            NumError::Parse(e)
+        }
+    }
+
+    fn at_most_100(n: i32) -> Result<i32, NumError> {
+        if n > 100 {
+            Err(NumError::TooLarge(n))
+        } else {
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_try_fn_stops_at_first_error() {
+        let from_str = i32::from_str;
+        let num_parse = try_f!(|s: &str| from_str(s)).err_into::<NumError>() >> at_most_100;
+
+        assert_eq!(num_parse("10"), Ok(10));
+        assert!(matches!(
+            num_parse("THIS IS NOT A NUMBER"),
+            Err(NumError::Parse(_))
+        ));
+        assert_eq!(num_parse("1000"), Err(NumError::TooLarge(1000)));
+    }
+
+    #[test]
+    fn test_try_fn_converts_errors_with_from() {
+        fn first_word(s: &str) -> &str {
+            s.split_whitespace().next().unwrap_or("")
+        }
+
+        let pipeline = TryFn::lift(first_word) >> i32::from_str >> at_most_100;
+
+        assert_eq!(pipeline("100 THIS IS A NUMBER"), Ok(100));
+        assert!(matches!(
+            pipeline("100THIS IS NOT A NUMBER"),
+            Err(NumError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_try_fn_map_and_map_err() {
+        let pipeline = try_f!(|s: &str| s.parse::<i32>())
+            .map(|n: i32| n * 2)
+            .map_err(|e: ParseIntError| e.to_string());
+
+        assert_eq!(pipeline.apply("21"), Ok(42));
+        assert_eq!(
+            pipeline.apply(""),
+            Err("cannot parse integer from empty string".to_string())
+        );
+    }
+
+    #[test]
+    fn test_try_fn_from_composable_fn() {
+        let parse = f!(|s: &str| s.parse::<i32>());
+        let pipeline = TryFn::from(parse).err_into::<NumError>() >> at_most_100;
+        let is_valid = ComposableFn::from(pipeline) >> |r: Result<i32, NumError>| r.is_ok();
+
+        assert!(is_valid("100"));
+        assert!(!is_valid("200"));
+    }
+
+    #[test]
+    fn test_opt_fn_stops_at_first_none() {
+        fn first_word(s: &str) -> Option<&str> {
+            s.split_whitespace().next()
+        }
+
+        let first_word_parse = opt_f!(first_word) >> |w: &str| w.parse::<i32>().ok();
+
+        assert_eq!(first_word_parse("100 THIS IS A NUMBER"), Some(100));
+        assert_eq!(first_word_parse("100THIS IS NOT A NUMBER"), None);
+        assert_eq!(first_word_parse("   "), None);
+    }
+
+    #[test]
+    fn test_opt_fn_alternatives() {
+        let english = HashMap::from([("one", 1), ("two", 2)]);
+        let dutch = HashMap::from([("een", 1), ("twee", 2)]);
+        let in_dutch = |s: &str| dutch.get(s).copied();
+        let digits = |s: &str| s.parse::<i32>().ok();
+
+        let number = opt_f!(|s: &str| english.get(s).copied()) | in_dutch | digits;
+        let or_zero = OptFn::lift(|s: &str| s.len()).or_else(|_: &str| Some(0));
+
+        assert_eq!(number("two"), Some(2));
+        assert_eq!(number("een"), Some(1));
+        assert_eq!(number("3"), Some(3));
+        assert_eq!(number("drie"), None);
+        assert_eq!(or_zero("abc"), Some(3));
+    }
+
+    #[test]
+    fn test_opt_fn_with_try_fn() {
+        let ids = HashMap::from([("ada", "36"), ("alan", "forty")]);
+        let lookup = opt_f!(|name: &str| ids.get(name).copied()).ok_or(NumError::TooLarge(0));
+        let age = lookup >> i32::from_str >> at_most_100;
+        let age_if_known = age.ok().map(|n: i32| n + 1);
+
+        assert_eq!(age_if_known("ada"), Some(37));
+        assert_eq!(age_if_known("alan"), None);
+        assert_eq!(age_if_known("grace"), None);
+    }
+
+    #[test]
+    fn test_opt_fn_ok_or_else_and_composable_fn() {
+        let parse = OptFn::from(f!(|s: &str| s.parse::<u8>().ok()));
+        let checked = parse.ok_or_else(|| "not a byte".to_string());
+
+        assert_eq!(checked("255"), Ok(255));
+        assert_eq!(checked("256"), Err("not a byte".to_string()));
+        assert!(
+            ComposableFn::from(OptFn::lift(str::len) >> |n: usize| n.checked_sub(1))("").is_none()
+        );
+    }
+}
@@ -1,5 +1,129 @@
-#![feature(fn_traits)]
-#![feature(unboxed_closures)]
+#![cfg_attr(feature = "nightly", feature(fn_traits, unboxed_closures))]
+
+//! Functional programming helpers: function composition, currying and
+//! higher-order function wrappers.
+//!
+//! The crate builds on stable Rust by default. Enabling the `nightly` cargo
+//! feature additionally implements the `Fn` traits on the function wrappers,
+//! so they can be passed anywhere a closure is expected.
+
+// Lets the code generated by the procedural macros refer to `::functional_rs`
+// from inside this crate as well.
+extern crate self as functional_rs;
+
+/// Implements calling for a unary function wrapper `$name<'a, T, U>` whose
+/// field dereferences to `dyn Fn(T) -> U + $bounds`.
+///
+/// Wrappers with other type parameters give them and their output type
+/// explicitly, as in `impl_unary_fn!(TryFn<U, E> => Result<U, E>)`.
+macro_rules! impl_unary_fn {
+    ($name:ident $(, $bound:path)*) => {
+        impl_unary_fn!($name<U> => U $(, $bound)*);
+    };
+    ($name:ident<$($param:ident),+> => $output:ty $(, $bound:path)*) => {
+        impl<'a, T, $($param),+> $name<'a, T, $($param),+> {
+            /// Applies the wrapped function to `x`.
+            pub fn apply(&self, x: T) -> $output {
+                (self.0)(x)
+            }
+        }
+
+        impl<'a, T, $($param),+> std::ops::Deref for $name<'a, T, $($param),+> {
+            type Target = dyn Fn(T) -> $output $(+ $bound)* + 'a;
+
+            fn deref(&self) -> &Self::Target {
+                &*self.0
+            }
+        }
+
+        #[cfg(not(feature = "nightly"))]
+        impl<'a, T, $($param),+> $crate::ApplyOnce<(T,)> for $name<'a, T, $($param),+> {
+            type Output = $output;
+
+            fn apply_once(self, args: (T,)) -> $output {
+                (self.0)(args.0)
+            }
+        }
+
+        #[cfg(not(feature = "nightly"))]
+        impl<'a, T, $($param),+> $crate::ApplyMut<(T,)> for $name<'a, T, $($param),+> {
+            fn apply_mut(&mut self, args: (T,)) -> $output {
+                (self.0)(args.0)
+            }
+        }
+
+        #[cfg(not(feature = "nightly"))]
+        impl<'a, T, $($param),+> $crate::Apply<(T,)> for $name<'a, T, $($param),+> {
+            fn apply(&self, args: (T,)) -> $output {
+                (self.0)(args.0)
+            }
+        }
+
+        #[cfg(feature = "nightly")]
+        impl<'a, T, $($param),+> Fn<(T,)> for $name<'a, T, $($param),+> {
+            extern "rust-call" fn call(&self, args: (T,)) -> $output {
+                (self.0)(args.0)
+            }
+        }
+
+        #[cfg(feature = "nightly")]
+        impl<'a, T, $($param),+> FnMut<(T,)> for $name<'a, T, $($param),+> {
+            extern "rust-call" fn call_mut(&mut self, args: (T,)) -> $output {
+                (self.0)(args.0)
+            }
+        }
+
+        #[cfg(feature = "nightly")]
+        impl<'a, T, $($param),+> FnOnce<(T,)> for $name<'a, T, $($param),+> {
+            type Output = $output;
+
+            extern "rust-call" fn call_once(self, args: (T,)) -> $output {
+                (self.0)(args.0)
+            }
+        }
+    };
+}
+
+mod arrow;
+mod async_fn;
+pub mod combinators;
+pub mod comparator;
+mod compose;
+mod curry;
+mod either;
+mod kleisli;
+pub mod monad;
+pub mod monoid;
+mod pred;
+mod shared;
+mod spread;
+mod stateful;
+mod sync;
+pub mod validation;
+
+#[doc(hidden)]
+pub mod __private {
+    /// Lets a return-position `impl Trait` capture the lifetime `'a` without
+    /// an outlives bound, for code generated by `#[curry(Self)]`.
+    pub trait Captures<'a> {}
+
+    impl<T: ?Sized> Captures<'_> for T {}
+}
+
+pub use async_fn::{AsyncComposableFn, AsyncSendFn, BoxFuture, SendBoxFuture};
+pub use comparator::Comparator;
+pub use compose::Compose;
+pub use curry::{Curry, Uncurry};
+pub use either::Either;
+pub use functional_rs_macros::{c, curry, Monoid, Semigroup};
+pub use kleisli::{OptFn, TryFn};
+pub use monad::{Applicative, Functor, Monad, MonadZero};
+pub use monoid::{Monoid, MonoidIter, Semigroup};
+pub use pred::Pred;
+pub use shared::{ArcFn, RcFn};
+pub use stateful::{ComposableFnMut, ComposableFnOnce};
+pub use sync::{SendFn, SyncFn};
+pub use validation::{NonEmpty, ValidFn, Validation};
 
 /// This macro creates a `ComposableFn` wrapper for a closure.
 /// It takes a closure expression and wraps it into a `ComposableFn` type,
@@ -21,6 +145,35 @@
 ///   let composed = add >> multiply; // First add, then multiply
 ///   assert_eq!(composed(5), 12); // (5 + 1) * 2 = 12
 ///   ```
+///
+///   Only the first stage has to be wrapped: the right-hand side of `>>` can be any
+///   function of one argument, such as a fn item, a closure or a function curried by `c!`.
+///
+///   ```rust
+///   use functional_rs::{c, f, ComposableFn};
+///   let add = c!(|a: i32, b: i32| a + b);
+///   let composed = f!(|x: i32| x - 20) >> add(1) >> i32::abs;
+///   assert_eq!(composed(5), 14); // |(5 - 20) + 1| = 14
+///   ```
+///
+/// - **`<<` (Left composition)**:
+///   The `<<` operator composes in mathematical order, like Haskell's `.`. `f2 << f1` applies `f1` first,
+///   and then applies `f2` to the result of `f1`, so it is interchangeable with `f1 >> f2`.
+///
+///   Unlike `>>`, `<<` only accepts a `ComposableFn` on its right: the argument type of the
+///   resulting function would come from that operand, which a generic function type does not
+///   determine. Wrap plain functions and closures with `f!` first.
+///
+///   Example:
+///   ```rust
+///   use functional_rs::{f, ComposableFn};
+///   let add = f!(|x: i32| x + 1);
+///   let multiply = f!(|x: i32| x * 2);
+///   let composed = multiply << add; // First add, then multiply
+///   assert_eq!(composed(5), 12); // (5 + 1) * 2 = 12
+///   let negated = f!(i32::wrapping_neg) << f!(|x: i32| x * 3);
+///   assert_eq!(negated(2), -6);
+///   ```
 #[macro_export]
 macro_rules! f {
     ($f:expr) => {
@@ -28,89 +181,198 @@ macro_rules! f {
     };
 }
 
-/// This macro curries a function, allowing partial application of arguments.
-/// It can handle various forms of argument types and function bodies.
-///
-/// ### Example
-/// The `curry` macro allows you to create curried versions of functions, enabling partial application of arguments.
+/// This macro composes any number of functions into a single `ComposableFn`,
+/// applying them from left to right. `compose!(a, b, c)` is equivalent to
+/// `f!(a) >> b >> c`.
 ///
 /// ```rust
-/// // Import the `curry` macro
-/// use functional_rs::c;
+/// use functional_rs::{c, compose};
+/// use std::str::FromStr;
 ///
-/// // Curry a simple function that adds two numbers
+/// let parse_or_zero = |result: Result<i32, _>| result.unwrap_or(0);
 /// let add = c!(|a: i32, b: i32| a + b);
-/// let add_5 = add(5); // Partially apply `5` to the function
-/// assert_eq!(add_5(3), 8); // 5 + 3 = 8
+/// let add_10_from_str = compose!(i32::from_str, parse_or_zero, add(10));
+/// assert_eq!(add_10_from_str("4"), 14);
 /// ```
 #[macro_export]
-macro_rules! c (
-    (|$first_arg:ident $(, $arg:ident )*| $function_body:expr) => {
-       move |$first_arg| $(move |$arg|)* {
-          $function_body
-       }
+macro_rules! compose {
+    ($first:expr $(, $rest:expr)* $(,)?) => {
+        $crate::ComposableFn(Box::new($first)) $(>> $rest)*
     };
-    (|$first_arg:ident:$first_arg_type:ty $(, $arg:ident:$arg_type:ty )*| $function_body:expr) => {
-      move |$first_arg:$first_arg_type| $(move |$arg:$arg_type|)* {
-         $function_body
-      }
-   };
-   (|$first_arg:ident:$first_arg_type:ty $(, $arg:ident:$arg_type:ty )*| -> $ret_type:ty $function_body:block) => {
-    move |$first_arg:$first_arg_type| $(move |$arg:$arg_type|)* -> $ret_type {
-       $function_body
+}
+
+/// This macro applies a value through a sequence of functions immediately,
+/// without building a boxed pipeline. `pipe!(x => a => b)` is `b(a(x))`.
+///
+/// Any function of one argument can be used as a stage, including the crate's
+/// own wrappers and functions curried by `c!`.
+///
+/// ```rust
+/// use functional_rs::{c, pipe};
+/// let add = c!(|a: i32, b: i32| a + b);
+/// let double = |x: i32| x * 2;
+/// assert_eq!(pipe!(4 => add(1) => double => i32::wrapping_neg), -10);
+/// ```
+#[macro_export]
+macro_rules! pipe {
+    ($value:expr) => {
+        $value
     };
-   };
-);
+    ($value:expr => $stage:expr $(=> $rest:expr)*) => {
+        $crate::pipe!($crate::Apply::apply(&$stage, ($value,)) $(=> $rest)*)
+    };
+}
+
+/// This macro partially applies a function, fixing any of its arguments.
+/// Each `_` marks an argument that stays open, in order: `p!(sub, _, 10)` is
+/// `|a| sub(a, 10)`.
+///
+/// With exactly one open argument the result is a `ComposableFn`, so it can be
+/// used directly in a `>>` pipeline. Otherwise it is a closure taking the open
+/// arguments.
+///
+/// The function and the fixed arguments are evaluated once, when the macro is
+/// used. Since the result can be called any number of times, the fixed
+/// arguments are cloned on every call and must be `Clone`.
+///
+/// ```rust
+/// use functional_rs::{f, p, ComposableFn};
+/// let sub = |a: i32, b: i32| a - b;
+/// let minus_10 = p!(sub, _, 10);
+/// let pipeline = f!(|x: i32| x * 2) >> minus_10;
+/// assert_eq!(pipeline(20), 30);
+///
+/// let clamp = |low: i32, x: i32, high: i32| x.max(low).min(high);
+/// let clamp_0 = p!(clamp, 0, _, _);
+/// assert_eq!(clamp_0(-5, 10), 0);
+/// ```
+#[macro_export]
+macro_rules! p {
+    ($f:expr $(, $($args:tt)*)?) => {
+        $crate::p!(@parse [$f] [] [] []; $($($args)*)?)
+    };
+    (@parse [$f:expr] [$($params:ident)*] [$($call:tt)*] [$($fixed:tt)*]; _ $(, $($rest:tt)*)?) => {
+        $crate::p!(@parse [$f] [$($params)* open] [$($call)* (open)] [$($fixed)*]; $($($rest)*)?)
+    };
+    (@parse [$f:expr] [$($params:ident)*] [$($call:tt)*] [$($fixed:tt)*]; $arg:expr $(, $($rest:tt)*)?) => {
+        $crate::p!(
+            @parse [$f] [$($params)*]
+            [$($call)* (::core::clone::Clone::clone(&fixed))]
+            [$($fixed)* let fixed = $arg;];
+            $($($rest)*)?
+        )
+    };
+    (@parse [$f:expr] [$param:ident] [$($call:tt)*] [$($fixed:tt)*];) => {{
+        let f = $f;
+        $($fixed)*
+        $crate::ComposableFn(Box::new(move |$param| f($($call),*)))
+    }};
+    (@parse [$f:expr] [$($params:ident)*] [$($call:tt)*] [$($fixed:tt)*];) => {{
+        let f = $f;
+        $($fixed)*
+        move |$($params),*| f($($call),*)
+    }};
+}
 
 /// `ComposableFn` is a function wrapper that allows functions to be composed
 /// using the `>>` operator. This enables chaining functions in a
 /// readable manner, where functions can be combined to process data step by step.
 /// It allows you to easily chain transformations or computations by passing the
 /// output of one function to the input of the next.
+///
+/// A `ComposableFn` can always be called directly, as in `composed(5)`: on
+/// stable it dereferences to the wrapped `dyn Fn(T) -> U`, and with the
+/// `nightly` feature it implements the `Fn` traits itself. The [`apply`]
+/// method is available in both modes.
+///
+/// ```rust
+/// use functional_rs::{f, ComposableFn};
+/// let double = f!(|x: i32| x * 2);
+/// assert_eq!(double.apply(21), 42);
+/// assert_eq!(double(21), 42);
+/// ```
+///
+/// [`apply`]: ComposableFn::apply
 pub struct ComposableFn<'a, T, U>(pub Box<dyn Fn(T) -> U + 'a>);
 
-impl<'a, T, U> Fn<(T,)> for ComposableFn<'a, T, U>
+impl_unary_fn!(ComposableFn);
+
+/// A function that can be used as a pipeline stage.
+///
+/// `Apply`, [`ApplyMut`] and [`ApplyOnce`] mirror `Fn`, `FnMut` and `FnOnce`:
+/// they take their arguments as a tuple, and are implemented for every function
+/// implementing the matching `Fn` trait. On stable Rust the crate's own function
+/// wrappers cannot implement the `Fn` traits, so they implement these traits
+/// directly; with the `nightly` feature they are covered by their `Fn`
+/// implementations.
+pub trait Apply<Args>: ApplyMut<Args> {
+    fn apply(&self, args: Args) -> Self::Output;
+}
+
+/// A function that may mutate its state when applied. See [`Apply`].
+pub trait ApplyMut<Args>: ApplyOnce<Args> {
+    fn apply_mut(&mut self, args: Args) -> Self::Output;
+}
+
+/// A function that may only be applied once. See [`Apply`].
+pub trait ApplyOnce<Args> {
+    type Output;
+
+    fn apply_once(self, args: Args) -> Self::Output;
+}
+
+impl<T, U, F> Apply<(T,)> for F
 where
-    T: 'a,
-    U: 'a,
+    F: Fn(T) -> U,
 {
-    extern "rust-call" fn call(&self, args: (T,)) -> U {
-        (self.0)(args.0)
+    fn apply(&self, args: (T,)) -> U {
+        self(args.0)
     }
 }
 
-impl<'a, T, U> FnMut<(T,)> for ComposableFn<'a, T, U>
+impl<T, U, F> ApplyMut<(T,)> for F
 where
-    T: 'a,
-    U: 'a,
+    F: FnMut(T) -> U,
 {
-    extern "rust-call" fn call_mut(&mut self, args: (T,)) -> U {
-        (self.0)(args.0)
+    fn apply_mut(&mut self, args: (T,)) -> U {
+        self(args.0)
     }
 }
 
-impl<'a, T, U> FnOnce<(T,)> for ComposableFn<'a, T, U>
+impl<T, U, F> ApplyOnce<(T,)> for F
+where
+    F: FnOnce(T) -> U,
+{
+    type Output = U;
+
+    fn apply_once(self, args: (T,)) -> U {
+        self(args.0)
+    }
+}
+
+impl<'a, T, U, G> std::ops::Shr<G> for ComposableFn<'a, T, U>
 where
     T: 'a,
     U: 'a,
+    G: Apply<(U,)> + 'a,
 {
-    type Output = U;
+    type Output = ComposableFn<'a, T, G::Output>;
 
-    extern "rust-call" fn call_once(self, args: (T,)) -> U {
-        (self.0)(args.0)
+    fn shr(self, rhs: G) -> Self::Output {
+        ComposableFn(Box::new(move |x: T| rhs.apply(((self.0)(x),))))
     }
 }
 
-impl<'a, T, U, V> std::ops::Shr<ComposableFn<'a, U, V>> for ComposableFn<'a, T, U>
+impl<'a, S, T, U> std::ops::Shl<ComposableFn<'a, S, T>> for ComposableFn<'a, T, U>
 where
+    S: 'a,
     T: 'a,
     U: 'a,
-    V: 'a,
 {
-    type Output = ComposableFn<'a, T, V>;
+    type Output = ComposableFn<'a, S, U>;
 
-    fn shr(self, rhs: ComposableFn<'a, U, V>) -> Self::Output {
-        ComposableFn(Box::new(move |x: T| rhs.0((self.0)(x))))
+    fn shl(self, rhs: ComposableFn<'a, S, T>) -> Self::Output {
+        ComposableFn(Box::new(move |x: S| (self.0)(rhs.0(x))))
     }
 }
 
@@ -166,4 +428,335 @@ mod tests {
 
         assert_eq!(add_10_from_str("4"), 14);
     }
+
+    #[test]
+    fn test_apply_matches_call() {
+        let add_one = f!(|x: i32| x + 1);
+        let double = f!(|x: i32| x * 2);
+        let composed = add_one >> double;
+
+        assert_eq!(composed.apply(5), composed(5));
+        assert_eq!([1, 2, 3].map(&*composed), [4, 6, 8]);
+    }
+
+    #[test]
+    fn test_shl_matches_shr() {
+        let from_str = i32::from_str;
+        let parse_or_zero = |result: Result<i32, <i32 as FromStr>::Err>| result.unwrap_or(0);
+        let add = c!(|a: i32, b: i32| a + b);
+        let forward = f!(from_str) >> f!(parse_or_zero) >> f!(add(10));
+        let backward = f!(add(10)) << f!(parse_or_zero) << f!(from_str);
+
+        for input in ["4", "-12", "not a number"] {
+            assert_eq!(forward(input), backward(input));
+        }
+    }
+
+    #[test]
+    fn test_shl_applies_right_operand_first() {
+        let add_one = f!(|x: i32| x + 1);
+        let double = f!(|x: i32| x * 2);
+        let to_string = f!(|x: i32| x.to_string());
+        let composed = to_string << double << add_one;
+
+        assert_eq!(composed(5), "12");
+    }
+
+    #[test]
+    fn test_shr_plain_stages() {
+        let from_str = i32::from_str;
+        let parse_or_zero = |result: Result<i32, <i32 as FromStr>::Err>| result.unwrap_or(0);
+        let add = c!(|a: i32, b: i32| a + b);
+        let add_10_from_str = f!(from_str) >> parse_or_zero >> add(10);
+
+        assert_eq!(add_10_from_str("4"), 14);
+        assert_eq!(add_10_from_str("not a number"), 10);
+    }
+
+    #[test]
+    fn test_shr_fn_items() {
+        fn double(x: i32) -> i32 {
+            x * 2
+        }
+        let describe = f!(|s: &str| s.len() as i32) >> double >> i32::wrapping_neg;
+
+        assert_eq!(describe("abc"), -6);
+    }
+
+    #[test]
+    fn test_shr_mixed_wrapped_and_plain_stages() {
+        let add = c!(|a: i32, b: i32| a + b);
+        let composed = f!(|x: i32| x * 3) >> add(1) >> f!(|x: i32| x - 2) >> |x| x * 10;
+
+        assert_eq!(composed(2), 50);
+    }
+
+    #[test]
+    fn test_compose_macro() {
+        let from_str = i32::from_str;
+        let parse_or_zero = |result: Result<i32, <i32 as FromStr>::Err>| result.unwrap_or(0);
+        let add = c!(|a: i32, b: i32| a + b);
+        let mul = c!(|a: i32, b: i32| a * b);
+        let pipeline = compose!(from_str, parse_or_zero, add(10), mul(2), |x: i32| x - 1,);
+
+        assert_eq!(pipeline("4"), 27);
+        assert_eq!(pipeline("not a number"), 19);
+    }
+
+    #[test]
+    fn test_compose_macro_single_stage() {
+        let len = compose!(|s: &str| s.len());
+
+        assert_eq!(len("abc"), 3);
+    }
+
+    #[test]
+    fn test_pipe_macro() {
+        let from_str = i32::from_str;
+        let parse_or_zero = |result: Result<i32, <i32 as FromStr>::Err>| result.unwrap_or(0);
+        let add = c!(|a: i32, b: i32| a + b);
+
+        assert_eq!(pipe!("4" => from_str => parse_or_zero => add(10)), 14);
+        assert_eq!(pipe!(5), 5);
+    }
+
+    #[test]
+    fn test_pipe_macro_with_wrapped_stages() {
+        let double = f!(|x: i32| x * 2);
+        let shout = compose!(|x: i32| x.to_string(), |s: String| s + "!");
+
+        assert_eq!(pipe!(3 => double => |x: i32| x + 1 => shout), "7!");
+        assert_eq!(double(1), 2);
+    }
+
+    #[test]
+    fn test_p_macro_single_open_argument() {
+        let sub = |a: i32, b: i32| a - b;
+        let minus_10 = p!(sub, _, 10);
+        let from_10 = p!(sub, 10, _);
+        let from_str = i32::from_str;
+        let parse_or_zero = |result: Result<i32, <i32 as FromStr>::Err>| result.unwrap_or(0);
+        let pipeline = f!(from_str) >> parse_or_zero >> minus_10 >> from_10;
+
+        assert_eq!(pipeline("15"), 5);
+        assert_eq!(pipeline("x"), 20);
+    }
+
+    #[test]
+    fn test_p_macro_several_open_arguments() {
+        fn clamp(low: i32, x: i32, high: i32) -> i32 {
+            x.max(low).min(high)
+        }
+
+        let clamp_0 = p!(clamp, 0, _, _);
+        let reorder = p!(clamp, _, 5, _);
+
+        assert_eq!(clamp_0(-5, 10), 0);
+        assert_eq!(clamp_0(15, 10), 10);
+        assert_eq!(reorder(0, 3), 3);
+        assert_eq!(p!(clamp, 0, 5, 10)(), 5);
+    }
+
+    #[test]
+    fn test_p_macro_evaluates_fixed_arguments_once() {
+        let mut evaluated = 0;
+        let mut greeting = || {
+            evaluated += 1;
+            String::from("hello")
+        };
+        let greet = p!(
+            |g: String, name: &str| format!("{g}, {name}"),
+            greeting(),
+            _,
+        );
+
+        assert_eq!(greet("ada"), "hello, ada");
+        assert_eq!(greet("alan"), "hello, alan");
+        assert_eq!(evaluated, 1);
+    }
+
+    #[test]
+    fn test_c_macro_untyped_arguments() {
+        let add = c!(|a, b| a + b);
+
+        assert_eq!(add(2)(3), 5);
+    }
+
+    #[test]
+    fn test_c_macro_patterns() {
+        let translate = c!(|(x, y): (i32, i32), (dx, dy): (i32, i32)| (x + dx, y + dy));
+        let first = c!(|[head, ..]: [u8; 3], _: ()| head);
+
+        assert_eq!(translate((1, 2))((10, 20)), (11, 22));
+        assert_eq!(first([7, 8, 9])(()), 7);
+    }
+
+    #[test]
+    fn test_c_macro_mut_bindings() {
+        let scale = c!(|mut x: i32, factor: i32| {
+            x *= factor;
+            x
+        });
+        let bump = c!(|(mut x, y): (i32, i32), mut by: i32| {
+            x += by;
+            by = 0;
+            (x, y + by)
+        });
+        let push = c!(|mut v: Vec<i32>, n: i32| {
+            v.push(n);
+            v.len()
+        });
+        let push_two = c!(|mut v: Vec<i32>, a: i32, b: i32| {
+            v.push(a);
+            v.push(b);
+            v.len()
+        });
+        let triple_of_2 = scale(2);
+        let push_onto_1 = push(vec![1]);
+        let push_onto_empty = push_two(vec![]);
+
+        assert_eq!(triple_of_2(3), 6);
+        assert_eq!(triple_of_2(3), 6);
+        assert_eq!(bump((1, 1))(5), (6, 1));
+        assert_eq!(push_onto_1(2), 2);
+        assert_eq!(push_onto_1(3), 2);
+        assert_eq!(push_onto_empty(1)(2), 2);
+        assert_eq!(push_onto_empty(3)(4), 2);
+    }
+
+    #[test]
+    fn test_c_macro_mixed_typed_and_untyped_arguments() {
+        let clamp = c!(|low: i32, high, x: i32| x.max(low).min(high));
+
+        assert_eq!(clamp(0)(10)(15), 10);
+        assert_eq!(clamp(0)(10)(-5), 0);
+    }
+
+    #[test]
+    fn test_c_macro_return_type_with_expression_body() {
+        let average = c!(|a: f64, b| -> f64 (a + b) / 2.0);
+        let describe = c!(|name: &'static str, age: u32| -> String format!("{name} ({age})"));
+        let from_str = i32::from_str;
+        let parse_or_zero = |result: Result<i32, <i32 as FromStr>::Err>| result.unwrap_or(0);
+        let sum = c!(|a, b: i32| -> i32 { a + b });
+
+        assert_eq!(average(1.0)(2.0), 1.5);
+        assert_eq!(describe("ada")(36), "ada (36)");
+        assert_eq!((f!(from_str) >> parse_or_zero >> sum(1))("2"), 3);
+    }
+
+    #[test]
+    fn test_c_macro_generic_argument_types() {
+        fn pair_with<T: Clone + 'static>(first: T) -> impl Fn(T) -> (T, T) {
+            let pair = c!(|a: T, b: T| (a.clone(), b));
+            pair(first)
+        }
+
+        assert_eq!(pair_with("x")("y"), ("x", "y"));
+    }
+
+    #[test]
+    fn test_curry_attribute() {
+        #[curry]
+        fn add(a: i32, b: i32) -> i32 {
+            a + b
+        }
+
+        let add_10 = add_c(10);
+        let parse_or_zero = |result: Result<i32, <i32 as FromStr>::Err>| result.unwrap_or(0);
+        let add_10_from_str = f!(i32::from_str) >> parse_or_zero >> add_c(10);
+
+        assert_eq!(add_10(4), add(10, 4));
+        assert_eq!(add_10_from_str("4"), 14);
+    }
+
+    #[test]
+    fn test_curry_attribute_three_arguments() {
+        #[curry]
+        fn clamp(low: i32, high: i32, x: i32) -> i32 {
+            x.max(low).min(high)
+        }
+
+        let percent = clamp_c(0)(100);
+
+        assert_eq!(percent(150), 100);
+        assert_eq!((f!(|x: i32| x * 10) >> percent)(5), 50);
+    }
+
+    #[test]
+    fn test_curry_attribute_generics() {
+        #[curry]
+        fn join<T, S>(separator: S, items: Vec<T>, last: T) -> String
+        where
+            T: ToString + Clone,
+            S: AsRef<str> + Clone,
+        {
+            let mut items = items;
+            items.push(last);
+            let items: Vec<String> = items.iter().map(T::to_string).collect();
+            items.join(separator.as_ref())
+        }
+
+        assert_eq!(join_c(", ")(vec![1, 2])(3), "1, 2, 3");
+    }
+
+    #[test]
+    fn test_curry_attribute_associated_functions() {
+        struct Point(i32, i32);
+
+        impl Point {
+            #[curry(Self)]
+            fn new(x: i32, y: i32) -> Self {
+                Point(x, y)
+            }
+        }
+
+        struct Wrapper<T>(T);
+
+        impl<T: Clone> Wrapper<T> {
+            #[curry(Self)]
+            fn pair(a: T, b: T) -> (T, T) {
+                (a, b)
+            }
+        }
+
+        let on_x_axis = Point::new_c(3);
+        let point = on_x_axis(0);
+        assert_eq!((point.0, point.1), (3, 0));
+        assert_eq!(Wrapper::<&str>::pair_c("a")("b"), ("a", "b"));
+    }
+
+    #[test]
+    fn test_curry_attribute_references() {
+        #[curry]
+        fn skip(s: &str, n: usize) -> &str {
+            &s[n..]
+        }
+
+        #[curry]
+        fn longest<'a>(a: &'a str, b: &'a str, min: usize) -> Option<&'a str> {
+            Some(if a.len() >= b.len() { a } else { b }).filter(|s| s.len() >= min)
+        }
+
+        let text = String::from("hello");
+        assert_eq!(skip_c(&text)(2), "llo");
+        assert_eq!(longest_c("ab")("abc")(3), Some("abc"));
+        assert_eq!(longest_c("ab")("abc")(4), None);
+    }
+
+    #[test]
+    fn test_curry_attribute_impl_trait_and_patterns() {
+        #[curry]
+        fn label(prefix: impl std::fmt::Display + Clone, (x, y): (i32, i32)) -> String {
+            format!("{prefix}: {x},{y}")
+        }
+
+        #[curry]
+        fn take<const N: usize>(items: [u8; N], count: usize) -> usize {
+            items.iter().take(count).count()
+        }
+
+        assert_eq!(label_c("point")((1, 2)), "point: 1,2");
+        assert_eq!(take_c([1, 2, 3])(2), 2);
+    }
 }
@@ -0,0 +1,638 @@
+//! `Functor`, `Applicative` and `Monad`: abstractions over containers.
+//!
+//! The traits are implemented for `Option`, `Result`, `Vec`, `Box` and
+//! `ComposableFn`, which is the reader functor: a `ComposableFn<R, A>` is a
+//! computation producing an `A` from a shared input `R`. Generic helpers like
+//! [`sequence`], [`traverse`], [`lift_a2`] and [`join`] are written once
+//! against the traits.
+//!
+//! Containers of different kinds are linked with the generic associated type
+//! `Wrapped<B>`, which is the same container holding a `B` instead.
+//!
+//! ```rust
+//! use functional_rs::monad::{sequence, traverse};
+//!
+//! let parsed = traverse(["1", "2", "3"], |s| s.parse::<i32>().ok());
+//! assert_eq!(parsed, Some(vec![1, 2, 3]));
+//! assert_eq!(sequence(vec![Some(1), None]), None);
+//! ```
+
+use crate::ComposableFn;
+
+/// This macro is do-notation for monads, turning a sequence of steps into
+/// nested calls of [`Monad::bind`] instead of nested `and_then` closures.
+///
+/// The steps are separated by `;`:
+///
+/// - `pattern <- expr;` binds the value of a monadic expression, and `_ <- expr;`
+///   runs one for its effect only.
+/// - `let pattern = expr;` binds a plain value.
+/// - `guard condition;` stops with [`MonadZero::zero`], like `None` or an empty
+///   `Vec`, if the condition is false.
+/// - The last step is either `ret expr` (or `pure expr`), which wraps a plain
+///   value with [`Applicative::pure`], or a monadic expression.
+///
+/// ```rust
+/// use functional_rs::mdo;
+///
+/// let area = |w: &str, h: &str| -> Option<u32> {
+///     mdo! {
+///         w <- w.parse::<u32>().ok();
+///         h <- h.parse::<u32>().ok();
+///         let area = w * h;
+///         guard area > 0;
+///         ret area
+///     }
+/// };
+/// assert_eq!(area("3", "4"), Some(12));
+/// assert_eq!(area("3", "x"), None);
+/// assert_eq!(area("0", "4"), None);
+/// ```
+///
+/// Starting with `iter =>` desugars to `flat_map` on iterators instead, for
+/// any `IntoIterator`. `ret` then yields a single item.
+///
+/// ```rust
+/// use functional_rs::mdo;
+///
+/// let triples: Vec<_> = mdo! { iter =>
+///     c <- 1..=20;
+///     b <- 1..c;
+///     a <- 1..b;
+///     guard a * a + b * b == c * c;
+///     ret (a, b, c)
+/// }
+/// .collect();
+/// assert_eq!(triples, [(3, 4, 5), (6, 8, 10), (5, 12, 13), (9, 12, 15), (8, 15, 17), (12, 16, 20)]);
+/// ```
+///
+/// Every step after a binding runs in a closure that may be called many
+/// times, for example once per element of a `Vec`. Values bound in earlier
+/// steps are moved into these closures, so they need to be `Copy`, or cloned
+/// when they are used.
+#[macro_export]
+macro_rules! mdo {
+    (iter => $($steps:tt)+) => {
+        $crate::mdo!(@iter $($steps)+)
+    };
+
+    (@iter let $p:pat = $e:expr; $($rest:tt)+) => {{
+        let $p = $e;
+        $crate::mdo!(@iter $($rest)+)
+    }};
+    (@iter guard $cond:expr; $($rest:tt)+) => {
+        ::core::iter::IntoIterator::into_iter(if $cond { Some(()) } else { None })
+            .flat_map(move |()| $crate::mdo!(@iter $($rest)+))
+    };
+    (@iter ret $e:expr) => {
+        ::core::iter::once($e)
+    };
+    (@iter pure $e:expr) => {
+        ::core::iter::once($e)
+    };
+    (@iter $($step:tt)+) => {
+        $crate::mdo!(@iter_bind [] $($step)+)
+    };
+    (@iter_bind [$($p:tt)+] <- $e:expr; $($rest:tt)+) => {
+        ::core::iter::IntoIterator::into_iter($e)
+            .flat_map(move |$($p)+| $crate::mdo!(@iter $($rest)+))
+    };
+    (@iter_bind [$($p:tt)*] $t:tt $($rest:tt)*) => {
+        $crate::mdo!(@iter_bind [$($p)* $t] $($rest)*)
+    };
+    (@iter_bind [$($e:tt)+]) => {
+        ::core::iter::IntoIterator::into_iter($($e)+)
+    };
+
+    (let $p:pat = $e:expr; $($rest:tt)+) => {{
+        let $p = $e;
+        $crate::mdo!($($rest)+)
+    }};
+    (guard $cond:expr; $($rest:tt)+) => {
+        if $cond {
+            $crate::mdo!($($rest)+)
+        } else {
+            $crate::monad::MonadZero::zero()
+        }
+    };
+    (ret $e:expr) => {
+        $crate::monad::Applicative::pure($e)
+    };
+    (pure $e:expr) => {
+        $crate::monad::Applicative::pure($e)
+    };
+    (@bind [$($p:tt)+] <- $e:expr; $($rest:tt)+) => {
+        $crate::monad::Monad::bind($e, move |$($p)+| $crate::mdo!($($rest)+))
+    };
+    (@bind [$($p:tt)*] $t:tt $($rest:tt)*) => {
+        $crate::mdo!(@bind [$($p)* $t] $($rest)*)
+    };
+    (@bind [$($e:tt)+]) => {
+        $($e)+
+    };
+    ($($step:tt)+) => {
+        $crate::mdo!(@bind [] $($step)+)
+    };
+}
+
+/// A container whose values can be transformed without changing its shape.
+pub trait Functor<'a> {
+    /// The type of the values in the container.
+    type Inner: 'a;
+
+    /// The same kind of container holding values of type `B`.
+    type Wrapped<B: 'a>: Functor<'a, Inner = B>;
+
+    /// Applies `f` to every value in the container.
+    ///
+    /// ```rust
+    /// use functional_rs::Functor;
+    /// assert_eq!(vec![1, 2].fmap(|x| x * 10), [10, 20]);
+    /// assert_eq!(Some("abc").fmap(str::len), Some(3));
+    /// ```
+    fn fmap<B, F>(self, f: F) -> Self::Wrapped<B>
+    where
+        B: 'a,
+        F: Fn(Self::Inner) -> B + 'a;
+}
+
+/// A functor that can hold a single value, and combine two containers into a
+/// container of pairs.
+pub trait Applicative<'a>: Functor<'a> {
+    /// Wraps `x` in the container.
+    fn pure(x: Self::Inner) -> Self;
+
+    /// Pairs the values of two containers. `Vec` pairs every value of `self`
+    /// with every value of `other`, so the values of `other` must be `Clone`.
+    ///
+    /// ```rust
+    /// use functional_rs::Applicative;
+    /// assert_eq!(Some(1).zip(Some("a")), Some((1, "a")));
+    /// assert_eq!(vec![1, 2].zip(vec!['a', 'b']), [(1, 'a'), (1, 'b'), (2, 'a'), (2, 'b')]);
+    /// ```
+    fn zip<B>(self, other: Self::Wrapped<B>) -> Self::Wrapped<(Self::Inner, B)>
+    where
+        B: Clone + 'a;
+}
+
+/// An applicative functor whose values can be chained with functions
+/// returning new containers.
+pub trait Monad<'a>: Applicative<'a> {
+    /// Applies `f` to every value in the container, and flattens the results.
+    ///
+    /// ```rust
+    /// use functional_rs::Monad;
+    /// assert_eq!(vec![1, 2].bind(|x| vec![x; x]), [1, 2, 2]);
+    /// assert_eq!(Some("4").bind(|s| s.parse::<i32>().ok()), Some(4));
+    /// ```
+    fn bind<B, F>(self, f: F) -> Self::Wrapped<B>
+    where
+        B: 'a,
+        F: Fn(Self::Inner) -> Self::Wrapped<B> + 'a;
+}
+
+/// A monad with an empty container, which [`mdo!`] uses for failed guards.
+///
+/// [`mdo!`]: crate::mdo
+pub trait MonadZero<'a>: Monad<'a> {
+    /// The container without any values.
+    fn zero() -> Self;
+}
+
+/// Combines the values of two containers with `f`.
+///
+/// ```rust
+/// use functional_rs::monad::lift_a2;
+/// assert_eq!(lift_a2(|a: i32, b: i32| a + b, Some(1), Some(2)), Some(3));
+/// assert_eq!(lift_a2(|a: i32, b: i32| a + b, Ok::<_, &str>(1), Err("bad")), Err("bad"));
+/// ```
+pub fn lift_a2<'a, M, B, C, F>(f: F, a: M, b: M::Wrapped<B>) -> M::Wrapped<C>
+where
+    M: Applicative<'a>,
+    B: Clone + 'a,
+    C: 'a,
+    F: Fn(M::Inner, B) -> C + 'a,
+    M::Wrapped<(M::Inner, B)>: Functor<'a, Wrapped<C> = M::Wrapped<C>>,
+{
+    a.zip(b).fmap(move |(x, y)| f(x, y))
+}
+
+/// Turns a sequence of containers into a container of a `Vec`, for example
+/// a list of `Option`s into an `Option` of a list, which is `None` if any of
+/// the values is `None`.
+///
+/// ```rust
+/// use functional_rs::monad::sequence;
+/// assert_eq!(sequence(vec![Ok(1), Ok(2)]), Ok::<_, String>(vec![1, 2]));
+/// assert_eq!(sequence(vec![vec![1, 2], vec![3]]), [[1, 3], [2, 3]]);
+/// ```
+pub fn sequence<'a, M, I>(items: I) -> M::Wrapped<Vec<M::Inner>>
+where
+    M: Applicative<'a>,
+    M::Inner: Clone,
+    I: IntoIterator<Item = M>,
+    M::Wrapped<Vec<M::Inner>>: Applicative<
+        'a,
+        Inner = Vec<M::Inner>,
+        Wrapped<M::Inner> = M,
+        Wrapped<Vec<M::Inner>> = M::Wrapped<Vec<M::Inner>>,
+    >,
+    <M::Wrapped<Vec<M::Inner>> as Functor<'a>>::Wrapped<(Vec<M::Inner>, M::Inner)>:
+        Functor<'a, Wrapped<Vec<M::Inner>> = M::Wrapped<Vec<M::Inner>>>,
+{
+    items
+        .into_iter()
+        .fold(Applicative::pure(Vec::new()), |acc, item| {
+            lift_a2(
+                |mut values: Vec<M::Inner>, x| {
+                    values.push(x);
+                    values
+                },
+                acc,
+                item,
+            )
+        })
+}
+
+/// Maps every value of `items` to a container with `f`, and then
+/// [`sequence`]s the results.
+///
+/// ```rust
+/// use functional_rs::monad::traverse;
+/// let parsed = traverse(["1", "x"], |s| s.parse::<i32>());
+/// assert!(parsed.is_err());
+/// ```
+pub fn traverse<'a, T, M, I, F>(items: I, f: F) -> M::Wrapped<Vec<M::Inner>>
+where
+    M: Applicative<'a>,
+    M::Inner: Clone,
+    I: IntoIterator<Item = T>,
+    F: FnMut(T) -> M,
+    M::Wrapped<Vec<M::Inner>>: Applicative<
+        'a,
+        Inner = Vec<M::Inner>,
+        Wrapped<M::Inner> = M,
+        Wrapped<Vec<M::Inner>> = M::Wrapped<Vec<M::Inner>>,
+    >,
+    <M::Wrapped<Vec<M::Inner>> as Functor<'a>>::Wrapped<(Vec<M::Inner>, M::Inner)>:
+        Functor<'a, Wrapped<Vec<M::Inner>> = M::Wrapped<Vec<M::Inner>>>,
+{
+    sequence(items.into_iter().map(f))
+}
+
+/// Flattens a container of containers by one level.
+///
+/// ```rust
+/// use functional_rs::monad::join;
+/// assert_eq!(join(Some(Some(1))), Some(1));
+/// assert_eq!(join(vec![vec![1], vec![2, 3]]), [1, 2, 3]);
+/// ```
+pub fn join<'a, M, N>(m: M) -> N
+where
+    M: Monad<'a, Inner = N, Wrapped<N::Inner> = N>,
+    N: Functor<'a>,
+{
+    m.bind::<N::Inner, _>(|n| n)
+}
+
+impl<'a, A: 'a> Functor<'a> for Option<A> {
+    type Inner = A;
+    type Wrapped<B: 'a> = Option<B>;
+
+    fn fmap<B, F>(self, f: F) -> Option<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        self.map(f)
+    }
+}
+
+impl<'a, A: 'a> Applicative<'a> for Option<A> {
+    fn pure(x: A) -> Self {
+        Some(x)
+    }
+
+    fn zip<B>(self, other: Option<B>) -> Option<(A, B)>
+    where
+        B: Clone + 'a,
+    {
+        Option::zip(self, other)
+    }
+}
+
+impl<'a, A: 'a> Monad<'a> for Option<A> {
+    fn bind<B, F>(self, f: F) -> Option<B>
+    where
+        B: 'a,
+        F: Fn(A) -> Option<B> + 'a,
+    {
+        self.and_then(f)
+    }
+}
+
+impl<'a, A: 'a> MonadZero<'a> for Option<A> {
+    fn zero() -> Self {
+        None
+    }
+}
+
+impl<'a, A: 'a, E: 'a> Functor<'a> for Result<A, E> {
+    type Inner = A;
+    type Wrapped<B: 'a> = Result<B, E>;
+
+    fn fmap<B, F>(self, f: F) -> Result<B, E>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        self.map(f)
+    }
+}
+
+impl<'a, A: 'a, E: 'a> Applicative<'a> for Result<A, E> {
+    fn pure(x: A) -> Self {
+        Ok(x)
+    }
+
+    fn zip<B>(self, other: Result<B, E>) -> Result<(A, B), E>
+    where
+        B: Clone + 'a,
+    {
+        Ok((self?, other?))
+    }
+}
+
+impl<'a, A: 'a, E: 'a> Monad<'a> for Result<A, E> {
+    fn bind<B, F>(self, f: F) -> Result<B, E>
+    where
+        B: 'a,
+        F: Fn(A) -> Result<B, E> + 'a,
+    {
+        self.and_then(f)
+    }
+}
+
+impl<'a, A: 'a> Functor<'a> for Vec<A> {
+    type Inner = A;
+    type Wrapped<B: 'a> = Vec<B>;
+
+    fn fmap<B, F>(self, f: F) -> Vec<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        self.into_iter().map(f).collect()
+    }
+}
+
+impl<'a, A: Clone + 'a> Applicative<'a> for Vec<A> {
+    fn pure(x: A) -> Self {
+        vec![x]
+    }
+
+    fn zip<B>(self, other: Vec<B>) -> Vec<(A, B)>
+    where
+        B: Clone + 'a,
+    {
+        self.into_iter()
+            .flat_map(|x| other.iter().map(move |y| (x.clone(), y.clone())))
+            .collect()
+    }
+}
+
+impl<'a, A: Clone + 'a> Monad<'a> for Vec<A> {
+    fn bind<B, F>(self, f: F) -> Vec<B>
+    where
+        B: 'a,
+        F: Fn(A) -> Vec<B> + 'a,
+    {
+        self.into_iter().flat_map(f).collect()
+    }
+}
+
+impl<'a, A: Clone + 'a> MonadZero<'a> for Vec<A> {
+    fn zero() -> Self {
+        Vec::new()
+    }
+}
+
+impl<'a, A: 'a> Functor<'a> for Box<A> {
+    type Inner = A;
+    type Wrapped<B: 'a> = Box<B>;
+
+    fn fmap<B, F>(self, f: F) -> Box<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        Box::new(f(*self))
+    }
+}
+
+impl<'a, A: 'a> Applicative<'a> for Box<A> {
+    fn pure(x: A) -> Self {
+        Box::new(x)
+    }
+
+    fn zip<B>(self, other: Box<B>) -> Box<(A, B)>
+    where
+        B: Clone + 'a,
+    {
+        Box::new((*self, *other))
+    }
+}
+
+impl<'a, A: 'a> Monad<'a> for Box<A> {
+    fn bind<B, F>(self, f: F) -> Box<B>
+    where
+        B: 'a,
+        F: Fn(A) -> Box<B> + 'a,
+    {
+        f(*self)
+    }
+}
+
+/// The reader functor: `fmap` post-composes a function.
+impl<'a, R: 'a, A: 'a> Functor<'a> for ComposableFn<'a, R, A> {
+    type Inner = A;
+    type Wrapped<B: 'a> = ComposableFn<'a, R, B>;
+
+    fn fmap<B, F>(self, f: F) -> ComposableFn<'a, R, B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        ComposableFn(Box::new(move |r| f((self.0)(r))))
+    }
+}
+
+/// `pure` ignores the input and returns a clone of the value, and `zip`
+/// passes a clone of the input to both functions.
+impl<'a, R: Clone + 'a, A: Clone + 'a> Applicative<'a> for ComposableFn<'a, R, A> {
+    fn pure(x: A) -> Self {
+        ComposableFn(Box::new(move |_| x.clone()))
+    }
+
+    fn zip<B>(self, other: ComposableFn<'a, R, B>) -> ComposableFn<'a, R, (A, B)>
+    where
+        B: Clone + 'a,
+    {
+        ComposableFn(Box::new(move |r: R| ((self.0)(r.clone()), (other.0)(r))))
+    }
+}
+
+/// `bind` passes the same input to this function and to the function it
+/// returns.
+impl<'a, R: Clone + 'a, A: Clone + 'a> Monad<'a> for ComposableFn<'a, R, A> {
+    fn bind<B, F>(self, f: F) -> ComposableFn<'a, R, B>
+    where
+        B: 'a,
+        F: Fn(A) -> ComposableFn<'a, R, B> + 'a,
+    {
+        ComposableFn(Box::new(move |r: R| (f((self.0)(r.clone())).0)(r)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::f;
+
+    #[test]
+    fn test_functor_and_monad_laws_for_option() {
+        let half = |x: i32| if x % 2 == 0 { Some(x / 2) } else { None };
+
+        assert_eq!(Some(4).fmap(|x| x), Some(4));
+        assert_eq!(Some(4).fmap(|x| x + 1).fmap(|x| x * 2), Some(10));
+        assert_eq!(Option::pure(8).bind(half), half(8));
+        assert_eq!(Some(8).bind(Option::pure), Some(8));
+        assert_eq!(
+            Some(8).bind(half).bind(half),
+            Some(8).bind(move |x| half(x).bind(half))
+        );
+        assert_eq!(Some(6).bind(half).bind(half), None);
+    }
+
+    #[test]
+    fn test_result_short_circuits() {
+        let parse = |s: &str| s.parse::<i32>().map_err(|e| e.to_string());
+
+        assert_eq!(traverse(["1", "2"], parse), Ok(vec![1, 2]));
+        assert!(traverse(["1", "x", ""], parse).is_err());
+        assert_eq!(
+            lift_a2(|a: i32, b: i32| a * b, parse("3"), parse("4")),
+            Ok(12)
+        );
+        assert_eq!(join(Ok::<_, String>(parse("5"))), Ok(5));
+    }
+
+    #[test]
+    fn test_vec_combines_every_value() {
+        let sums = lift_a2(|a: i32, b: i32| a + b, vec![0, 10], vec![1, 2]);
+        let pairs = sequence(vec![vec!['a', 'b'], vec!['x', 'y']]);
+
+        assert_eq!(sums, [1, 2, 11, 12]);
+        assert_eq!(pairs, [['a', 'x'], ['a', 'y'], ['b', 'x'], ['b', 'y']]);
+        assert_eq!(sequence(Vec::<Vec<i32>>::new()), [Vec::<i32>::new()]);
+        assert_eq!(vec![1, 2, 3].bind(|x| vec![x; x as usize]).len(), 6);
+    }
+
+    #[test]
+    fn test_box() {
+        let boxed = Box::new(2).fmap(|x| x * 3).bind(|x| Box::new(x + 1));
+
+        assert_eq!(*boxed, 7);
+        assert_eq!(*join(Box::new(Box::new("a"))), "a");
+        assert_eq!(*sequence([Box::new(1), Box::new(2)]), [1, 2]);
+    }
+
+    #[test]
+    fn test_mdo_option_and_result() {
+        let lookup = |key: &str| match key {
+            "a" => Some(1),
+            "b" => Some(2),
+            _ => None,
+        };
+        let sum = |x: &'static str, y: &'static str| {
+            mdo! {
+                a <- lookup(x);
+                b <- lookup(y);
+                ret a + b
+            }
+        };
+        let checked: Result<i32, String> = mdo! {
+            n <- "12".parse::<i32>().map_err(|e| e.to_string());
+            let half = n / 2;
+            _ <- if half > 0 { Ok(()) } else { Err("too small".to_string()) };
+            (low, high) <- Ok((half - 1, half + 1));
+            Ok(low * high)
+        };
+
+        assert_eq!(sum("a", "b"), Some(3));
+        assert_eq!(sum("a", "c"), None);
+        assert_eq!(checked, Ok(35));
+    }
+
+    #[test]
+    fn test_mdo_vec_guard() {
+        let pairs: Vec<(i32, i32)> = mdo! {
+            x <- vec![1, 2, 3];
+            y <- vec![1, 2, 3];
+            guard x < y;
+            pure (x, y)
+        };
+        let words = mdo! { iter =>
+            word <- ["ab", "c"];
+            c <- word.chars();
+            ret c.to_ascii_uppercase()
+        };
+
+        assert_eq!(pairs, [(1, 2), (1, 3), (2, 3)]);
+        assert_eq!(words.collect::<String>(), "ABC");
+    }
+
+    #[test]
+    fn test_mdo_reader() {
+        let config = (2, 10);
+        let scaled = mdo! {
+            factor <- f!(|c: &(i32, i32)| c.0);
+            limit <- f!(|c: &(i32, i32)| c.1);
+            ret (factor * limit).min(15)
+        };
+
+        assert_eq!(scaled(&config), 15);
+    }
+
+    #[test]
+    fn test_reader() {
+        struct Config {
+            name: &'static str,
+            retries: u32,
+        }
+
+        let config = Config {
+            name: "fetch",
+            retries: 2,
+        };
+        let unset = Config {
+            name: "",
+            retries: 0,
+        };
+        let name = f!(|c: &Config| c.name);
+        let retries = f!(|c: &Config| c.retries);
+        let summary = lift_a2(|n: &str, r: u32| format!("{n} x{r}"), name, retries);
+        let retries_or_default = f!(|c: &Config| c.retries == 0)
+            .bind(|unset| f!(move |c: &Config| if unset { 3 } else { c.retries }));
+        let both = sequence(vec![
+            f!(|c: &Config| c.retries),
+            f!(|c: &Config| c.retries * 2),
+        ]);
+
+        assert_eq!(summary(&config), "fetch x2");
+        assert_eq!(retries_or_default(&unset), 3);
+        assert_eq!(retries_or_default(&config), 2);
+        assert_eq!(both(&config), [2, 4]);
+        assert_eq!(ComposableFn::<&Config, _>::pure(1)(&config), 1);
+    }
+}
@@ -0,0 +1,372 @@
+//! `Semigroup` and `Monoid`: types whose values can be combined.
+//!
+//! Numbers can be combined in several ways, so they are wrapped in one of the
+//! [`Sum`], [`Product`], [`Min`] and [`Max`] newtypes first. Strings and
+//! `Vec`s are concatenated, `HashMap`s are merged, combining the values of
+//! shared keys, and `ComposableFn<T, T>` functions are composed with `>>`.
+//!
+//! `#[derive(Semigroup, Monoid)]` implements the traits for structs field by
+//! field.
+//!
+//! ```rust
+//! use functional_rs::monoid::{Max, Sum};
+//! use functional_rs::{Monoid, MonoidIter, Semigroup};
+//!
+//! #[derive(Semigroup, Monoid, Debug, PartialEq)]
+//! struct Stats {
+//!     count: Sum<u32>,
+//!     longest: Max<usize>,
+//!     words: Vec<String>,
+//! }
+//!
+//! let stats = ["a", "abc", "ab"].into_iter().fold_map(|word| Stats {
+//!     count: Sum(1),
+//!     longest: Max(word.len()),
+//!     words: vec![word.to_string()],
+//! });
+//! assert_eq!(stats.count, Sum(3));
+//! assert_eq!(stats.longest, Max(3));
+//! assert_eq!(stats.words, ["a", "abc", "ab"]);
+//! ```
+
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+use crate::ComposableFn;
+
+/// A type with an associative operation combining two values into one.
+pub trait Semigroup {
+    /// Combines `self` with `other`. For any `a`, `b` and `c`,
+    /// `a.combine(b).combine(c)` must equal `a.combine(b.combine(c))`.
+    fn combine(self, other: Self) -> Self;
+}
+
+/// A semigroup with an identity value.
+pub trait Monoid: Semigroup {
+    /// The value that leaves any other value unchanged when combined with it.
+    fn empty() -> Self;
+}
+
+/// Combines values of an iterator with their `Monoid` implementation.
+pub trait MonoidIter: Iterator {
+    /// Combines all items, from first to last, or returns `empty()` for an
+    /// empty iterator.
+    ///
+    /// ```rust
+    /// use functional_rs::MonoidIter;
+    /// let text = ["fold", "ing"].map(String::from).into_iter().mconcat();
+    /// assert_eq!(text, "folding");
+    /// ```
+    fn mconcat(self) -> Self::Item
+    where
+        Self: Sized,
+        Self::Item: Monoid,
+    {
+        self.fold(Monoid::empty(), Semigroup::combine)
+    }
+
+    /// Maps every item to a monoid with `f`, and combines the results.
+    ///
+    /// ```rust
+    /// use functional_rs::monoid::Product;
+    /// use functional_rs::MonoidIter;
+    /// assert_eq!((1..=5).fold_map(Product), Product(120));
+    /// ```
+    fn fold_map<M, F>(self, f: F) -> M
+    where
+        Self: Sized,
+        M: Monoid,
+        F: FnMut(Self::Item) -> M,
+    {
+        self.map(f).mconcat()
+    }
+}
+
+impl<I: Iterator> MonoidIter for I {}
+
+/// Combines numbers by adding them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Sum<T>(pub T);
+
+/// Combines numbers by multiplying them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Product<T>(pub T);
+
+/// Combines values by keeping the smallest one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Min<T>(pub T);
+
+/// Combines values by keeping the largest one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Max<T>(pub T);
+
+impl<T: std::ops::Add<Output = T>> Semigroup for Sum<T> {
+    fn combine(self, other: Self) -> Self {
+        Sum(self.0 + other.0)
+    }
+}
+
+impl<T: std::ops::Mul<Output = T>> Semigroup for Product<T> {
+    fn combine(self, other: Self) -> Self {
+        Product(self.0 * other.0)
+    }
+}
+
+impl<T: Ord> Semigroup for Min<T> {
+    fn combine(self, other: Self) -> Self {
+        Min(self.0.min(other.0))
+    }
+}
+
+impl<T: Ord> Semigroup for Max<T> {
+    fn combine(self, other: Self) -> Self {
+        Max(self.0.max(other.0))
+    }
+}
+
+macro_rules! impl_number_monoids {
+    (integers: $($int:ty)*; floats: $($float:ty)*) => {
+        $(
+            impl Monoid for Sum<$int> {
+                fn empty() -> Self {
+                    Sum(0)
+                }
+            }
+
+            impl Monoid for Product<$int> {
+                fn empty() -> Self {
+                    Product(1)
+                }
+            }
+
+            impl Monoid for Min<$int> {
+                fn empty() -> Self {
+                    Min(<$int>::MAX)
+                }
+            }
+
+            impl Monoid for Max<$int> {
+                fn empty() -> Self {
+                    Max(<$int>::MIN)
+                }
+            }
+        )*
+        $(
+            impl Monoid for Sum<$float> {
+                fn empty() -> Self {
+                    Sum(0.0)
+                }
+            }
+
+            impl Monoid for Product<$float> {
+                fn empty() -> Self {
+                    Product(1.0)
+                }
+            }
+        )*
+    };
+}
+
+impl_number_monoids!(
+    integers: i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize;
+    floats: f32 f64
+);
+
+impl Semigroup for String {
+    fn combine(mut self, other: Self) -> Self {
+        self.push_str(&other);
+        self
+    }
+}
+
+impl Monoid for String {
+    fn empty() -> Self {
+        String::new()
+    }
+}
+
+impl<T> Semigroup for Vec<T> {
+    fn combine(mut self, other: Self) -> Self {
+        self.extend(other);
+        self
+    }
+}
+
+impl<T> Monoid for Vec<T> {
+    fn empty() -> Self {
+        Vec::new()
+    }
+}
+
+/// Merges two maps, combining the values of keys present in both.
+impl<K, V, S> Semigroup for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: Semigroup,
+    S: BuildHasher,
+{
+    fn combine(mut self, other: Self) -> Self {
+        for (key, value) in other {
+            let value = match self.remove(&key) {
+                Some(old) => old.combine(value),
+                None => value,
+            };
+            self.insert(key, value);
+        }
+        self
+    }
+}
+
+impl<K, V, S> Monoid for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: Semigroup,
+    S: BuildHasher + Default,
+{
+    fn empty() -> Self {
+        HashMap::default()
+    }
+}
+
+/// Combines two `Some` values, treating `None` as the identity. This makes
+/// `Option<S>` a monoid for any semigroup `S`.
+impl<S: Semigroup> Semigroup for Option<S> {
+    fn combine(self, other: Self) -> Self {
+        match (self, other) {
+            (Some(a), Some(b)) => Some(a.combine(b)),
+            (a, None) => a,
+            (None, b) => b,
+        }
+    }
+}
+
+impl<S: Semigroup> Monoid for Option<S> {
+    fn empty() -> Self {
+        None
+    }
+}
+
+impl Semigroup for () {
+    fn combine(self, _: Self) -> Self {}
+}
+
+impl Monoid for () {
+    fn empty() -> Self {}
+}
+
+macro_rules! impl_tuple_monoids {
+    ($($name:ident $index:tt),+) => {
+        impl<$($name: Semigroup),+> Semigroup for ($($name,)+) {
+            fn combine(self, other: Self) -> Self {
+                ($(self.$index.combine(other.$index),)+)
+            }
+        }
+
+        impl<$($name: Monoid),+> Monoid for ($($name,)+) {
+            fn empty() -> Self {
+                ($($name::empty(),)+)
+            }
+        }
+    };
+}
+
+impl_tuple_monoids!(A 0);
+impl_tuple_monoids!(A 0, B 1);
+impl_tuple_monoids!(A 0, B 1, C 2);
+impl_tuple_monoids!(A 0, B 1, C 2, D 3);
+impl_tuple_monoids!(A 0, B 1, C 2, D 3, E 4);
+impl_tuple_monoids!(A 0, B 1, C 2, D 3, E 4, F 5);
+
+/// Composes two functions with `>>`: `f.combine(g)` applies `f` first.
+impl<'a, T: 'a> Semigroup for ComposableFn<'a, T, T> {
+    fn combine(self, other: Self) -> Self {
+        self >> other
+    }
+}
+
+/// The identity function.
+impl<'a, T: 'a> Monoid for ComposableFn<'a, T, T> {
+    fn empty() -> Self {
+        ComposableFn(Box::new(|x| x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{f, Monoid, Semigroup};
+
+    #[test]
+    fn test_numbers() {
+        let values = [3, -1, 4];
+
+        assert_eq!(values.into_iter().fold_map(Sum), Sum(6));
+        assert_eq!(values.into_iter().fold_map(Product), Product(-12));
+        assert_eq!(values.into_iter().fold_map(Min), Min(-1));
+        assert_eq!(values.into_iter().fold_map(Max), Max(4));
+        assert_eq!(std::iter::empty().fold_map(Min), Min(i32::MAX));
+        assert_eq!([0.5, 0.25].into_iter().fold_map(Sum), Sum(0.75));
+    }
+
+    #[test]
+    fn test_collections() {
+        let counts = |words: &[&str]| -> HashMap<String, Sum<u32>> {
+            words.iter().map(|w| (w.to_string(), Sum(1))).collect()
+        };
+        let merged = counts(&["a", "b"]).combine(counts(&["b", "c"]));
+
+        assert_eq!(merged["a"], Sum(1));
+        assert_eq!(merged["b"], Sum(2));
+        assert_eq!(merged.len(), 3);
+        assert_eq!(vec![1, 2].combine(vec![3]), [1, 2, 3]);
+        assert_eq!(String::from("ab").combine(String::empty()), "ab");
+    }
+
+    #[test]
+    fn test_option_and_tuples() {
+        let maxima = [Some(Max(2)), None, Some(Max(5))].into_iter().mconcat();
+        let pair = [(Sum(1), String::from("a")), (Sum(2), String::from("b"))]
+            .into_iter()
+            .mconcat();
+
+        assert_eq!(maxima, Some(Max(5)));
+        assert_eq!(Option::<Max<i32>>::empty(), None);
+        assert_eq!(pair, (Sum(3), String::from("ab")));
+        assert_eq!(<(Sum<i32>, Product<i32>)>::empty(), (Sum(0), Product(1)));
+    }
+
+    #[test]
+    fn test_endomorphisms() {
+        let steps = [
+            f!(|x: i32| x + 1),
+            f!(|x: i32| x * 10),
+            ComposableFn::empty(),
+        ];
+        let pipeline = steps.into_iter().mconcat();
+
+        assert_eq!(pipeline(1), 20);
+        assert_eq!(ComposableFn::<&str, &str>::empty()("id"), "id");
+    }
+
+    #[test]
+    fn test_derive() {
+        #[derive(Semigroup, Monoid, Debug, PartialEq)]
+        struct Totals(Sum<u32>, Option<Min<u32>>);
+
+        #[derive(Semigroup, Monoid, Debug, PartialEq)]
+        struct Report<T> {
+            totals: Totals,
+            items: Vec<T>,
+        }
+
+        let report = |price: u32| Report {
+            totals: Totals(Sum(price), Some(Min(price))),
+            items: vec![price],
+        };
+        let combined = [report(3), report(1), report(2)].into_iter().mconcat();
+
+        assert_eq!(combined.totals, Totals(Sum(6), Some(Min(1))));
+        assert_eq!(combined.items, [3, 1, 2]);
+        assert_eq!(Report::<u8>::empty().totals, Totals(Sum(0), None));
+    }
+}
@@ -0,0 +1,234 @@
+/// `Pred` is a predicate: a function wrapper testing a reference to a value.
+///
+/// Predicates combine with `!p`, `p & q`, `p | q` and `p ^ q`, and
+/// [`contramap`](Pred::contramap) tests a value through a projection.
+///
+/// A `Pred` dereferences to the wrapped `dyn Fn(&T) -> bool`, so `&*p` can be
+/// passed to `Iterator::filter` on stable. With the `nightly` feature it
+/// implements the `Fn` traits itself, and `filter(&p)` works directly.
+///
+/// ```rust
+/// use functional_rs::Pred;
+///
+/// let is_even = Pred::new(|x: &usize| x % 2 == 0);
+/// let is_short = Pred::new(|x: &usize| *x < 4);
+/// let even_length = (is_even & !is_short).contramap(String::len);
+///
+/// let words = ["tree", "hi", "sunday", "abc"].map(String::from);
+/// let kept: Vec<String> = words.into_iter().filter(&*even_length).collect();
+/// assert_eq!(kept, ["tree", "sunday"]);
+/// ```
+pub struct Pred<'a, T: ?Sized>(pub Box<dyn Fn(&T) -> bool + 'a>);
+
+impl<'a, T: ?Sized> Pred<'a, T> {
+    /// Creates a `Pred` from a function testing a reference.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&T) -> bool + 'a,
+    {
+        Pred(Box::new(f))
+    }
+
+    /// Tests `x` against the predicate.
+    pub fn apply(&self, x: &T) -> bool {
+        (self.0)(x)
+    }
+
+    /// Tests values of another type by projecting them with `f` first.
+    ///
+    /// ```rust
+    /// use functional_rs::Pred;
+    /// let is_even = Pred::new(|n: &usize| n % 2 == 0);
+    /// let even_length = is_even.contramap(str::len);
+    /// assert!(even_length.apply("ab"));
+    /// ```
+    pub fn contramap<S, F>(self, f: F) -> Pred<'a, S>
+    where
+        S: ?Sized,
+        T: Sized + 'a,
+        F: Fn(&S) -> T + 'a,
+    {
+        Pred(Box::new(move |x| (self.0)(&f(x))))
+    }
+
+    /// A predicate that holds if all of `preds` hold, and for no predicates
+    /// at all.
+    ///
+    /// ```rust
+    /// use functional_rs::Pred;
+    /// let in_range = Pred::all_of([Pred::new(|x: &i32| *x > 0), Pred::new(|x: &i32| *x < 10)]);
+    /// assert!(in_range.apply(&5));
+    /// assert!(!in_range.apply(&10));
+    /// ```
+    pub fn all_of<I>(preds: I) -> Self
+    where
+        T: 'a,
+        I: IntoIterator<Item = Pred<'a, T>>,
+    {
+        let preds: Vec<_> = preds.into_iter().collect();
+        Pred(Box::new(move |x| preds.iter().all(|p| (p.0)(x))))
+    }
+
+    /// A predicate that holds if any of `preds` holds. It never holds for no
+    /// predicates at all.
+    pub fn any_of<I>(preds: I) -> Self
+    where
+        T: 'a,
+        I: IntoIterator<Item = Pred<'a, T>>,
+    {
+        let preds: Vec<_> = preds.into_iter().collect();
+        Pred(Box::new(move |x| preds.iter().any(|p| (p.0)(x))))
+    }
+}
+
+impl<'a, T: ?Sized> std::ops::Deref for Pred<'a, T> {
+    type Target = dyn Fn(&T) -> bool + 'a;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl<'a, T: ?Sized + 'a> std::ops::Not for Pred<'a, T> {
+    type Output = Pred<'a, T>;
+
+    fn not(self) -> Self::Output {
+        Pred(Box::new(move |x| !(self.0)(x)))
+    }
+}
+
+impl<'a, T: ?Sized + 'a> std::ops::BitAnd for Pred<'a, T> {
+    type Output = Pred<'a, T>;
+
+    fn bitand(self, rhs: Pred<'a, T>) -> Self::Output {
+        Pred(Box::new(move |x| (self.0)(x) && (rhs.0)(x)))
+    }
+}
+
+impl<'a, T: ?Sized + 'a> std::ops::BitOr for Pred<'a, T> {
+    type Output = Pred<'a, T>;
+
+    fn bitor(self, rhs: Pred<'a, T>) -> Self::Output {
+        Pred(Box::new(move |x| (self.0)(x) || (rhs.0)(x)))
+    }
+}
+
+impl<'a, T: ?Sized + 'a> std::ops::BitXor for Pred<'a, T> {
+    type Output = Pred<'a, T>;
+
+    fn bitxor(self, rhs: Pred<'a, T>) -> Self::Output {
+        Pred(Box::new(move |x| (self.0)(x) != (rhs.0)(x)))
+    }
+}
+
+#[cfg(not(feature = "nightly"))]
+impl<'a, 'b, T: ?Sized> crate::ApplyOnce<(&'b T,)> for Pred<'a, T> {
+    type Output = bool;
+
+    fn apply_once(self, args: (&'b T,)) -> bool {
+        (self.0)(args.0)
+    }
+}
+
+#[cfg(not(feature = "nightly"))]
+impl<'a, 'b, T: ?Sized> crate::ApplyMut<(&'b T,)> for Pred<'a, T> {
+    fn apply_mut(&mut self, args: (&'b T,)) -> bool {
+        (self.0)(args.0)
+    }
+}
+
+#[cfg(not(feature = "nightly"))]
+impl<'a, 'b, T: ?Sized> crate::Apply<(&'b T,)> for Pred<'a, T> {
+    fn apply(&self, args: (&'b T,)) -> bool {
+        (self.0)(args.0)
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<'a, 'b, T: ?Sized> Fn<(&'b T,)> for Pred<'a, T> {
+    extern "rust-call" fn call(&self, args: (&'b T,)) -> bool {
+        (self.0)(args.0)
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<'a, 'b, T: ?Sized> FnMut<(&'b T,)> for Pred<'a, T> {
+    extern "rust-call" fn call_mut(&mut self, args: (&'b T,)) -> bool {
+        (self.0)(args.0)
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<'a, 'b, T: ?Sized> FnOnce<(&'b T,)> for Pred<'a, T> {
+    type Output = bool;
+
+    extern "rust-call" fn call_once(self, args: (&'b T,)) -> bool {
+        (self.0)(args.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{f, ComposableFn};
+
+    fn is_even<'a>() -> Pred<'a, i32> {
+        Pred::new(|x: &i32| x % 2 == 0)
+    }
+
+    fn is_positive<'a>() -> Pred<'a, i32> {
+        Pred::new(|x: &i32| *x > 0)
+    }
+
+    #[test]
+    fn test_pred_operators() {
+        let values = [-2, -1, 0, 1, 2, 3];
+        let keep = |p: Pred<i32>| values.iter().copied().filter(&*p).collect::<Vec<_>>();
+
+        assert_eq!(keep(!is_even()), [-1, 1, 3]);
+        assert_eq!(keep(is_even() & is_positive()), [2]);
+        assert_eq!(keep(is_even() | is_positive()), [-2, 0, 1, 2, 3]);
+        assert_eq!(keep(is_even() ^ is_positive()), [-2, 0, 1, 3]);
+    }
+
+    #[test]
+    fn test_pred_all_of_and_any_of() {
+        let small = Pred::new(|x: &i32| x.abs() < 3);
+        let all = Pred::all_of([is_even(), is_positive(), small]);
+        let any = Pred::any_of(vec![is_even(), is_positive()]);
+
+        assert!(all.apply(&2) && !all.apply(&4) && !all.apply(&-2));
+        assert!(any.apply(&-2) && any.apply(&3) && !any.apply(&-3));
+        assert!(Pred::<i32>::all_of([]).apply(&0));
+        assert!(!Pred::<i32>::any_of([]).apply(&0));
+    }
+
+    #[test]
+    fn test_pred_contramap_unsized() {
+        let long = Pred::new(|n: &usize| *n > 3).contramap(str::len);
+        let shouting = Pred::new(|s: &str| s.chars().all(char::is_uppercase));
+        let long_shout = long & shouting;
+
+        let words = ["HELLO", "HI", "hello"];
+        let kept: Vec<_> = words.into_iter().filter(|w| long_shout(w)).collect();
+        assert_eq!(kept, ["HELLO"]);
+    }
+
+    #[test]
+    fn test_pred_as_pipeline_stage() {
+        let is_blank = f!(str::trim) >> Pred::new(str::is_empty);
+
+        assert!(is_blank("   "));
+        assert!(!is_blank(" a "));
+    }
+
+    #[cfg(feature = "nightly")]
+    #[test]
+    fn test_pred_passed_to_filter_directly() {
+        let evens: Vec<i32> = (1..=6).filter(&is_even()).collect();
+        let odds: Vec<i32> = (1..=6).filter(!is_even()).collect();
+
+        assert_eq!(evens, [2, 4, 6]);
+        assert_eq!(odds, [1, 3, 5]);
+    }
+}
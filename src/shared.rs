@@ -0,0 +1,116 @@
+use crate::{Apply, ComposableFn, SyncFn};
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// `RcFn` is a reference-counted `ComposableFn`. Cloning it only bumps a
+/// reference count, so a pipeline prefix can be built once and branched into
+/// several pipelines.
+///
+/// ```rust
+/// use functional_rs::{f, ComposableFn};
+/// let parse = (f!(|s: &str| s.trim()) >> |s: &str| s.parse::<i32>().unwrap_or(0)).share();
+/// let doubled = parse.clone() >> |x: i32| x * 2;
+/// let negated = parse >> |x: i32| -x;
+/// assert_eq!(doubled(" 21 "), 42);
+/// assert_eq!(negated(" 21 "), -21);
+/// ```
+pub struct RcFn<'a, T, U>(pub Rc<dyn Fn(T) -> U + 'a>);
+
+/// `ArcFn` is the thread-safe counterpart of [`RcFn`], created from a
+/// [`SyncFn`]. Its clones can be sent to other threads.
+pub struct ArcFn<'a, T, U>(pub Arc<dyn Fn(T) -> U + Send + Sync + 'a>);
+
+impl_unary_fn!(RcFn);
+impl_unary_fn!(ArcFn, Send, Sync);
+
+impl<'a, T, U> Clone for RcFn<'a, T, U> {
+    fn clone(&self) -> Self {
+        RcFn(Rc::clone(&self.0))
+    }
+}
+
+impl<'a, T, U> Clone for ArcFn<'a, T, U> {
+    fn clone(&self) -> Self {
+        ArcFn(Arc::clone(&self.0))
+    }
+}
+
+impl<'a, T, U> ComposableFn<'a, T, U> {
+    /// Converts the pipeline into a cloneable [`RcFn`].
+    pub fn share(self) -> RcFn<'a, T, U> {
+        RcFn(Rc::from(self.0))
+    }
+}
+
+impl<'a, T, U> SyncFn<'a, T, U> {
+    /// Converts the pipeline into a cloneable [`ArcFn`].
+    pub fn share(self) -> ArcFn<'a, T, U> {
+        ArcFn(Arc::from(self.0))
+    }
+}
+
+impl<'a, T, U, G> std::ops::Shr<G> for RcFn<'a, T, U>
+where
+    T: 'a,
+    U: 'a,
+    G: Apply<(U,)> + 'a,
+{
+    type Output = RcFn<'a, T, G::Output>;
+
+    fn shr(self, rhs: G) -> Self::Output {
+        RcFn(Rc::new(move |x: T| rhs.apply(((self.0)(x),))))
+    }
+}
+
+impl<'a, T, U, G> std::ops::Shr<G> for ArcFn<'a, T, U>
+where
+    T: 'a,
+    U: 'a,
+    G: Apply<(U,)> + Send + Sync + 'a,
+{
+    type Output = ArcFn<'a, T, G::Output>;
+
+    fn shr(self, rhs: G) -> Self::Output {
+        ArcFn(Arc::new(move |x: T| rhs.apply(((self.0)(x),))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{f, f_sync};
+    use std::str::FromStr;
+    use std::thread;
+
+    #[test]
+    fn test_shared_prefix_branches() {
+        let from_str = i32::from_str;
+        let parse_or_zero = |result: Result<i32, <i32 as FromStr>::Err>| result.unwrap_or(0);
+        let num_parse = (f!(from_str) >> parse_or_zero).share();
+        let add_10 = num_parse.clone() >> |x: i32| x + 10;
+        let is_even = num_parse.clone() >> |x: i32| x % 2 == 0;
+
+        assert_eq!(add_10("4"), 14);
+        assert!(is_even("4"));
+        assert_eq!(num_parse("not a number"), 0);
+    }
+
+    #[test]
+    fn test_clone_shares_function() {
+        let shared = f!(|x: i32| x + 1).share();
+        let cloned = shared.clone();
+
+        assert!(Rc::ptr_eq(&shared.0, &cloned.0));
+        assert_eq!(Rc::strong_count(&shared.0), 2);
+    }
+
+    #[test]
+    fn test_arc_fn_branches_across_threads() {
+        let prefix = (f_sync!(|s: String| s.len()) >> |n: usize| n * 2).share();
+        let plus_one = prefix.clone() >> |n: usize| n + 1;
+        let handle = thread::spawn(move || plus_one("abc".to_string()));
+
+        assert_eq!(handle.join().unwrap(), 7);
+        assert_eq!(prefix("abcd".to_string()), 8);
+    }
+}
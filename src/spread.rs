@@ -0,0 +1,157 @@
+use crate::{Apply, ApplyMut, ApplyOnce, ComposableFn};
+
+impl<'a, Args, U> ComposableFn<'a, Args, U> {
+    /// Creates a `ComposableFn` over a tuple of arguments from a function taking
+    /// them separately, so a pipeline can start with a function of several
+    /// arguments without currying it first.
+    ///
+    /// With the `nightly` feature the pipeline is called with separate arguments,
+    /// as in `pipeline(a, b)`. On stable it is called with the tuple,
+    /// as in `pipeline((a, b))`.
+    ///
+    /// ```rust
+    /// use functional_rs::ComposableFn;
+    /// let sum_doubled = ComposableFn::spread(|a: i32, b: i32| a + b) >> |x: i32| x * 2;
+    /// assert_eq!(sum_doubled.apply((3, 4)), 14);
+    /// ```
+    pub fn spread<F>(f: F) -> Self
+    where
+        F: Apply<Args, Output = U> + 'a,
+    {
+        ComposableFn(Box::new(move |args: Args| f.apply(args)))
+    }
+}
+
+/// Implements the `Apply` traits for functions of several arguments, and
+/// spreads the tuple input of a `ComposableFn` into separate arguments.
+macro_rules! impl_spread {
+    ($($arg:ident),+) => {
+        impl<$($arg,)+ U, F> Apply<($($arg,)+)> for F
+        where
+            F: Fn($($arg),+) -> U,
+        {
+            #[allow(non_snake_case)]
+            fn apply(&self, args: ($($arg,)+)) -> U {
+                let ($($arg,)+) = args;
+                self($($arg),+)
+            }
+        }
+
+        impl<$($arg,)+ U, F> ApplyMut<($($arg,)+)> for F
+        where
+            F: FnMut($($arg),+) -> U,
+        {
+            #[allow(non_snake_case)]
+            fn apply_mut(&mut self, args: ($($arg,)+)) -> U {
+                let ($($arg,)+) = args;
+                self($($arg),+)
+            }
+        }
+
+        impl<$($arg,)+ U, F> ApplyOnce<($($arg,)+)> for F
+        where
+            F: FnOnce($($arg),+) -> U,
+        {
+            type Output = U;
+
+            #[allow(non_snake_case)]
+            fn apply_once(self, args: ($($arg,)+)) -> U {
+                let ($($arg,)+) = args;
+                self($($arg),+)
+            }
+        }
+
+        #[cfg(not(feature = "nightly"))]
+        impl<'a, $($arg,)+ U> Apply<($($arg,)+)> for ComposableFn<'a, ($($arg,)+), U> {
+            fn apply(&self, args: ($($arg,)+)) -> U {
+                (self.0)(args)
+            }
+        }
+
+        #[cfg(not(feature = "nightly"))]
+        impl<'a, $($arg,)+ U> ApplyMut<($($arg,)+)> for ComposableFn<'a, ($($arg,)+), U> {
+            fn apply_mut(&mut self, args: ($($arg,)+)) -> U {
+                (self.0)(args)
+            }
+        }
+
+        #[cfg(not(feature = "nightly"))]
+        impl<'a, $($arg,)+ U> ApplyOnce<($($arg,)+)> for ComposableFn<'a, ($($arg,)+), U> {
+            type Output = U;
+
+            fn apply_once(self, args: ($($arg,)+)) -> U {
+                (self.0)(args)
+            }
+        }
+
+        #[cfg(feature = "nightly")]
+        impl<'a, $($arg,)+ U> Fn<($($arg,)+)> for ComposableFn<'a, ($($arg,)+), U> {
+            extern "rust-call" fn call(&self, args: ($($arg,)+)) -> U {
+                (self.0)(args)
+            }
+        }
+
+        #[cfg(feature = "nightly")]
+        impl<'a, $($arg,)+ U> FnMut<($($arg,)+)> for ComposableFn<'a, ($($arg,)+), U> {
+            extern "rust-call" fn call_mut(&mut self, args: ($($arg,)+)) -> U {
+                (self.0)(args)
+            }
+        }
+
+        #[cfg(feature = "nightly")]
+        impl<'a, $($arg,)+ U> FnOnce<($($arg,)+)> for ComposableFn<'a, ($($arg,)+), U> {
+            type Output = U;
+
+            extern "rust-call" fn call_once(self, args: ($($arg,)+)) -> U {
+                (self.0)(args)
+            }
+        }
+    };
+}
+
+impl_spread!(A, B);
+impl_spread!(A, B, C);
+impl_spread!(A, B, C, D);
+impl_spread!(A, B, C, D, E);
+impl_spread!(A, B, C, D, E, G);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{c, f};
+
+    #[test]
+    fn test_spread_two_arguments() {
+        let add = ComposableFn::spread(|a: i32, b: i32| a + b);
+        let add_then_double = add >> |x: i32| x * 2;
+
+        assert_eq!(add_then_double((3, 4)), 14);
+        assert_eq!(add_then_double.apply((1, 1)), 4);
+    }
+
+    #[test]
+    fn test_spread_three_arguments_with_curried_stage() {
+        let mul = c!(|a: i32, b: i32| a * b);
+        let volume = ComposableFn::spread(|w: i32, h: i32, d: i32| w * h * d) >> mul(2);
+
+        assert_eq!(volume((2, 3, 4)), 48);
+    }
+
+    #[test]
+    fn test_spread_as_later_stage() {
+        let pair = |s: &str| (s.len(), s.chars().filter(|c| c.is_uppercase()).count());
+        let ratio = f!(pair) >> ComposableFn::spread(|len: usize, upper: usize| upper * 100 / len);
+
+        assert_eq!(ratio("AbCd"), 50);
+    }
+
+    #[cfg(feature = "nightly")]
+    #[test]
+    fn test_spread_called_with_separate_arguments() {
+        let describe = ComposableFn::spread(|name: &str, age: u32| format!("{name} ({age})"))
+            >> |s: String| s.to_uppercase();
+
+        assert_eq!(describe("ada", 36), "ADA (36)");
+        assert_eq!(describe(("ada", 36)), "ADA (36)");
+    }
+}
@@ -0,0 +1,280 @@
+use crate::{ApplyMut, ApplyOnce, ComposableFn};
+
+/// This macro creates a `ComposableFnMut` wrapper for a closure that mutates
+/// its captured state, such as a counter or a buffer.
+///
+/// ```rust
+/// use functional_rs::f_mut;
+/// let mut total = 0;
+/// let mut running_total = f_mut!(|x: i32| {
+///     total += x;
+///     total
+/// });
+/// assert_eq!(running_total.apply(2), 2);
+/// assert_eq!(running_total.apply(3), 5);
+/// ```
+#[macro_export]
+macro_rules! f_mut {
+    ($f:expr) => {
+        $crate::ComposableFnMut(Box::new($f))
+    };
+}
+
+/// This macro creates a `ComposableFnOnce` wrapper for a closure that consumes
+/// its captured state and can only be called once.
+///
+/// ```rust
+/// use functional_rs::f_once;
+/// let greeting = String::from("hello");
+/// let greet = f_once!(move |name: &str| greeting + " " + name);
+/// assert_eq!(greet.apply("world"), "hello world");
+/// ```
+#[macro_export]
+macro_rules! f_once {
+    ($f:expr) => {
+        $crate::ComposableFnOnce(Box::new($f))
+    };
+}
+
+/// `ComposableFnMut` is the `FnMut` counterpart of `ComposableFn`, for stages
+/// that keep mutable state between calls.
+///
+/// Composing with `>>` produces the weakest kind of its two operands: a
+/// `ComposableFnMut` composed with a `Fn` or `FnMut` stage is a `ComposableFnMut`,
+/// and so is a `ComposableFn` composed with a `ComposableFnMut`.
+pub struct ComposableFnMut<'a, T, U>(pub Box<dyn FnMut(T) -> U + 'a>);
+
+/// `ComposableFnOnce` is the `FnOnce` counterpart of `ComposableFn`, for stages
+/// that consume a moved resource.
+///
+/// Composing any stage with a `ComposableFnOnce`, on either side of `>>`,
+/// produces a `ComposableFnOnce`. It is called with [`apply`], or directly with
+/// the `nightly` feature.
+///
+/// [`apply`]: ComposableFnOnce::apply
+pub struct ComposableFnOnce<'a, T, U>(pub Box<dyn FnOnce(T) -> U + 'a>);
+
+impl<'a, T, U> ComposableFnMut<'a, T, U> {
+    /// Applies the wrapped function to `x`.
+    pub fn apply(&mut self, x: T) -> U {
+        (self.0)(x)
+    }
+}
+
+impl<'a, T, U> ComposableFnOnce<'a, T, U> {
+    /// Applies the wrapped function to `x`, consuming it.
+    pub fn apply(self, x: T) -> U {
+        (self.0)(x)
+    }
+}
+
+impl<'a, T, U> std::ops::Deref for ComposableFnMut<'a, T, U> {
+    type Target = dyn FnMut(T) -> U + 'a;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl<'a, T, U> std::ops::DerefMut for ComposableFnMut<'a, T, U> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut *self.0
+    }
+}
+
+#[cfg(not(feature = "nightly"))]
+impl<'a, T, U> ApplyMut<(T,)> for ComposableFnMut<'a, T, U> {
+    fn apply_mut(&mut self, args: (T,)) -> U {
+        (self.0)(args.0)
+    }
+}
+
+#[cfg(not(feature = "nightly"))]
+impl<'a, T, U> ApplyOnce<(T,)> for ComposableFnMut<'a, T, U> {
+    type Output = U;
+
+    fn apply_once(mut self, args: (T,)) -> U {
+        (self.0)(args.0)
+    }
+}
+
+#[cfg(not(feature = "nightly"))]
+impl<'a, T, U> ApplyOnce<(T,)> for ComposableFnOnce<'a, T, U> {
+    type Output = U;
+
+    fn apply_once(self, args: (T,)) -> U {
+        (self.0)(args.0)
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<'a, T, U> FnMut<(T,)> for ComposableFnMut<'a, T, U> {
+    extern "rust-call" fn call_mut(&mut self, args: (T,)) -> U {
+        (self.0)(args.0)
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<'a, T, U> FnOnce<(T,)> for ComposableFnMut<'a, T, U> {
+    type Output = U;
+
+    extern "rust-call" fn call_once(mut self, args: (T,)) -> U {
+        (self.0)(args.0)
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<'a, T, U> FnOnce<(T,)> for ComposableFnOnce<'a, T, U> {
+    type Output = U;
+
+    extern "rust-call" fn call_once(self, args: (T,)) -> U {
+        (self.0)(args.0)
+    }
+}
+
+impl<'a, T, U, V> std::ops::Shr<ComposableFnMut<'a, U, V>> for ComposableFn<'a, T, U>
+where
+    T: 'a,
+    U: 'a,
+    V: 'a,
+{
+    type Output = ComposableFnMut<'a, T, V>;
+
+    fn shr(self, mut rhs: ComposableFnMut<'a, U, V>) -> Self::Output {
+        ComposableFnMut(Box::new(move |x: T| (rhs.0)((self.0)(x))))
+    }
+}
+
+impl<'a, T, U, V> std::ops::Shr<ComposableFnOnce<'a, U, V>> for ComposableFn<'a, T, U>
+where
+    T: 'a,
+    U: 'a,
+    V: 'a,
+{
+    type Output = ComposableFnOnce<'a, T, V>;
+
+    fn shr(self, rhs: ComposableFnOnce<'a, U, V>) -> Self::Output {
+        ComposableFnOnce(Box::new(move |x: T| (rhs.0)((self.0)(x))))
+    }
+}
+
+impl<'a, T, U, G> std::ops::Shr<G> for ComposableFnMut<'a, T, U>
+where
+    T: 'a,
+    U: 'a,
+    G: ApplyMut<(U,)> + 'a,
+{
+    type Output = ComposableFnMut<'a, T, G::Output>;
+
+    fn shr(mut self, mut rhs: G) -> Self::Output {
+        ComposableFnMut(Box::new(move |x: T| rhs.apply_mut(((self.0)(x),))))
+    }
+}
+
+impl<'a, T, U, V> std::ops::Shr<ComposableFnOnce<'a, U, V>> for ComposableFnMut<'a, T, U>
+where
+    T: 'a,
+    U: 'a,
+    V: 'a,
+{
+    type Output = ComposableFnOnce<'a, T, V>;
+
+    fn shr(mut self, rhs: ComposableFnOnce<'a, U, V>) -> Self::Output {
+        ComposableFnOnce(Box::new(move |x: T| (rhs.0)((self.0)(x))))
+    }
+}
+
+impl<'a, T, U, G> std::ops::Shr<G> for ComposableFnOnce<'a, T, U>
+where
+    T: 'a,
+    U: 'a,
+    G: ApplyOnce<(U,)> + 'a,
+{
+    type Output = ComposableFnOnce<'a, T, G::Output>;
+
+    fn shr(self, rhs: G) -> Self::Output {
+        ComposableFnOnce(Box::new(move |x: T| rhs.apply_once(((self.0)(x),))))
+    }
+}
+
+impl<'a, T, U> From<ComposableFn<'a, T, U>> for ComposableFnMut<'a, T, U> {
+    fn from(f: ComposableFn<'a, T, U>) -> Self {
+        ComposableFnMut(f.0)
+    }
+}
+
+impl<'a, T, U> From<ComposableFn<'a, T, U>> for ComposableFnOnce<'a, T, U>
+where
+    T: 'a,
+    U: 'a,
+{
+    fn from(f: ComposableFn<'a, T, U>) -> Self {
+        ComposableFnOnce(f.0)
+    }
+}
+
+impl<'a, T, U> From<ComposableFnMut<'a, T, U>> for ComposableFnOnce<'a, T, U>
+where
+    T: 'a,
+    U: 'a,
+{
+    fn from(f: ComposableFnMut<'a, T, U>) -> Self {
+        ComposableFnOnce(f.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::f;
+
+    #[test]
+    fn test_fn_mut_counts_calls() {
+        let mut calls = 0;
+        {
+            let mut counted = f!(|x: i32| x + 1)
+                >> f_mut!(|x: i32| {
+                    calls += 1;
+                    x
+                })
+                >> |x: i32| x * 2;
+
+            assert_eq!(counted(1), 4);
+            assert_eq!(counted.apply(2), 6);
+        }
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn test_fn_mut_buffers_values() {
+        let mut buffer = Vec::new();
+        {
+            let mut collect = f_mut!(|s: &str| s.len()) >> f_mut!(|n: usize| buffer.push(n));
+            collect("a");
+            collect("abc");
+        }
+        assert_eq!(buffer, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_fn_once_consumes_resource() {
+        let names = ["a".to_string(), "b".to_string()];
+        let join = f_once!(move |sep: &str| names.join(sep));
+        let shout = f!(|s: &str| s.trim()) >> join >> |s: String| s.to_uppercase();
+
+        assert_eq!(shout.apply(" - "), "A-B");
+    }
+
+    #[test]
+    fn test_fn_mut_then_fn_once() {
+        let mut seen = 0;
+        let token = String::from("done");
+        let finish = f_mut!(|x: i32| {
+            seen += x;
+            seen
+        }) >> f_once!(move |total: i32| format!("{token}: {total}"));
+
+        assert_eq!(finish.apply(3), "done: 3");
+        assert_eq!(seen, 3);
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{Apply, ComposableFn};
+
+/// This macro creates a `SendFn` wrapper for a closure, the thread-safe
+/// counterpart of `f!`. The closure must be `Send`.
+///
+/// ```rust
+/// use functional_rs::f_send;
+/// let pipeline = f_send!(|x: i32| x + 1) >> |x: i32| x * 2;
+/// let handle = std::thread::spawn(move || pipeline(5));
+/// assert_eq!(handle.join().unwrap(), 12);
+/// ```
+#[macro_export]
+macro_rules! f_send {
+    ($f:expr) => {
+        $crate::SendFn(Box::new($f))
+    };
+}
+
+/// This macro creates a `SyncFn` wrapper for a closure. The closure must be
+/// `Send` and `Sync`, so the resulting pipeline can be shared behind an `Arc`.
+///
+/// ```rust
+/// use functional_rs::f_sync;
+/// use std::sync::Arc;
+/// let pipeline = Arc::new(f_sync!(|x: i32| x + 1) >> |x: i32| x * 2);
+/// let shared = Arc::clone(&pipeline);
+/// let handle = std::thread::spawn(move || shared(5));
+/// assert_eq!(handle.join().unwrap(), pipeline(5));
+/// ```
+#[macro_export]
+macro_rules! f_sync {
+    ($f:expr) => {
+        $crate::SyncFn(Box::new($f))
+    };
+}
+
+/// `SendFn` is a `ComposableFn` whose function is `Send`, so the pipeline can be
+/// moved to another thread.
+///
+/// Composing with `>>` only accepts `Send` stages and produces another `SendFn`.
+/// A stage that is not `Send` does not compile; to add one anyway, first convert
+/// the pipeline into a `ComposableFn`, which is not `Send` itself.
+pub struct SendFn<'a, T, U>(pub Box<dyn Fn(T) -> U + Send + 'a>);
+
+/// `SyncFn` is a `ComposableFn` whose function is `Send` and `Sync`, so the
+/// pipeline can be shared between threads, for example behind an `Arc`.
+///
+/// Composing with `>>` only accepts `Send + Sync` stages and produces another
+/// `SyncFn`. Convert it into a [`SendFn`] or a `ComposableFn` to add weaker stages.
+pub struct SyncFn<'a, T, U>(pub Box<dyn Fn(T) -> U + Send + Sync + 'a>);
+
+impl_unary_fn!(SendFn, Send);
+impl_unary_fn!(SyncFn, Send, Sync);
+
+impl<'a, T, U, G> std::ops::Shr<G> for SendFn<'a, T, U>
+where
+    T: 'a,
+    U: 'a,
+    G: Apply<(U,)> + Send + 'a,
+{
+    type Output = SendFn<'a, T, G::Output>;
+
+    fn shr(self, rhs: G) -> Self::Output {
+        SendFn(Box::new(move |x: T| rhs.apply(((self.0)(x),))))
+    }
+}
+
+impl<'a, T, U, G> std::ops::Shr<G> for SyncFn<'a, T, U>
+where
+    T: 'a,
+    U: 'a,
+    G: Apply<(U,)> + Send + Sync + 'a,
+{
+    type Output = SyncFn<'a, T, G::Output>;
+
+    fn shr(self, rhs: G) -> Self::Output {
+        SyncFn(Box::new(move |x: T| rhs.apply(((self.0)(x),))))
+    }
+}
+
+impl<'a, T, U> From<SendFn<'a, T, U>> for ComposableFn<'a, T, U> {
+    fn from(f: SendFn<'a, T, U>) -> Self {
+        ComposableFn(f.0)
+    }
+}
+
+impl<'a, T, U> From<SyncFn<'a, T, U>> for ComposableFn<'a, T, U> {
+    fn from(f: SyncFn<'a, T, U>) -> Self {
+        ComposableFn(f.0)
+    }
+}
+
+impl<'a, T, U> From<SyncFn<'a, T, U>> for SendFn<'a, T, U> {
+    fn from(f: SyncFn<'a, T, U>) -> Self {
+        SendFn(f.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::c;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_send_fn_moves_into_thread() {
+        let from_str = i32::from_str;
+        let parse_or_zero = |result: Result<i32, <i32 as FromStr>::Err>| result.unwrap_or(0);
+        let add = c!(|a: i32, b: i32| a + b);
+        let add_10_from_str = f_send!(from_str) >> parse_or_zero >> add(10);
+        let handle = thread::spawn(move || add_10_from_str("4"));
+
+        assert_eq!(handle.join().unwrap(), 14);
+    }
+
+    #[test]
+    fn test_sync_fn_shared_between_threads() {
+        let pipeline = Arc::new(f_sync!(|x: u64| x * 2) >> f_sync!(|x: u64| x + 1));
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let pipeline = Arc::clone(&pipeline);
+                thread::spawn(move || pipeline(i))
+            })
+            .collect();
+        let results: Vec<u64> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        assert_eq!(results, vec![1, 3, 5, 7]);
+    }
+
+    #[test]
+    fn test_weaker_stages_require_conversion() {
+        let counter = std::cell::Cell::new(0);
+        let count = |x: i32| {
+            counter.set(counter.get() + 1);
+            x
+        };
+        let sync = f_sync!(|x: i32| x + 1);
+        let send = SendFn::from(sync) >> f_send!(|x: i32| x * 2);
+        let local = ComposableFn::from(send) >> count;
+
+        assert_eq!(local(1), 4);
+        assert_eq!(counter.get(), 1);
+    }
+}
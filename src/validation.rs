@@ -0,0 +1,641 @@
+//! `Validation`: results that collect every error instead of stopping at the
+//! first one.
+//!
+//! Independent validations are combined with [`Validation::zip`], the
+//! [`map2`] to [`map8`] functions or the [`validate!`] macro, and the result
+//! is invalid with the errors of all of them if any of them is. Validations
+//! that depend on each other are chained with [`Validation::and_then`], which
+//! stops at the first invalid step like a `Result` does.
+//!
+//! [`ValidFn`] wraps a field validator, so it can be built as a pipeline.
+//!
+//! [`validate!`]: crate::validate
+
+use crate::monad::{Applicative, Functor};
+use crate::{Apply, ComposableFn, Semigroup};
+
+/// This macro creates a `ValidFn` wrapper for a closure returning a
+/// `Validation`.
+///
+/// ```rust
+/// use functional_rs::{valid_f, Validation};
+/// let positive = valid_f!(|n: i32| if n > 0 { Validation::Valid(n) } else { Validation::invalid("not positive") });
+/// assert!(positive(1).is_valid());
+/// assert!(positive(-1).is_invalid());
+/// ```
+#[macro_export]
+macro_rules! valid_f {
+    ($f:expr) => {
+        $crate::ValidFn(Box::new($f))
+    };
+}
+
+/// This macro combines independent validations, collecting the errors of all
+/// the invalid ones.
+///
+/// `validate!(a, b, c => f)` applies `f` to the values of up to eight
+/// validations, like [`map2`] to [`map8`]. `validate!(Struct { field: a, .. })`
+/// builds a struct from validations of its fields.
+///
+/// ```rust
+/// use functional_rs::{validate, Validation};
+///
+/// #[derive(Debug)]
+/// struct User {
+///     name: String,
+///     age: u8,
+/// }
+///
+/// let name = |s: &str| {
+///     if s.is_empty() {
+///         Validation::invalid("name is empty")
+///     } else {
+///         Validation::Valid(s.to_string())
+///     }
+/// };
+/// let age = |s: &str| Validation::from(s.parse::<u8>().map_err(|_| "age is not a number"));
+///
+/// let user = validate!(User { name: name("ada"), age: age("36") });
+/// assert_eq!(user.map(|u| u.age), Validation::Valid(36));
+///
+/// let user = validate!(User { name: name(""), age: age("old") });
+/// let errors: Vec<_> = user.unwrap_invalid().into_iter().collect();
+/// assert_eq!(errors, ["name is empty", "age is not a number"]);
+///
+/// let sum = validate!(age("1"), age("2"), age("3") => |a, b, c| a + b + c);
+/// assert_eq!(sum, Validation::Valid(6));
+/// ```
+#[macro_export]
+macro_rules! validate {
+    ($($name:ident)::+ { $($field:ident : $value:expr),+ $(,)? }) => {
+        $crate::validate!(@struct [$($name)::+] [$($field)+] [$($value),+])
+    };
+    (@struct [$($name:tt)+] [$first:ident $($field:ident)*] [$first_value:expr $(, $value:expr)*]) => {
+        $crate::Validation::map(
+            $first_value $(.zip($value))*,
+            |$crate::validate!(@pattern [$first] $($field)*)| $($name)+ { $first $(, $field)* },
+        )
+    };
+    (@pattern [$($pattern:tt)+] $next:ident $($field:ident)*) => {
+        $crate::validate!(@pattern [($($pattern)+, $next)] $($field)*)
+    };
+    (@pattern [$($pattern:tt)+]) => {
+        $($pattern)+
+    };
+    ($a:expr $(,)? => $f:expr) => {
+        $crate::Validation::map($a, $f)
+    };
+    ($a:expr, $b:expr $(,)? => $f:expr) => {
+        $crate::validation::map2($a, $b, $f)
+    };
+    ($a:expr, $b:expr, $c:expr $(,)? => $f:expr) => {
+        $crate::validation::map3($a, $b, $c, $f)
+    };
+    ($a:expr, $b:expr, $c:expr, $d:expr $(,)? => $f:expr) => {
+        $crate::validation::map4($a, $b, $c, $d, $f)
+    };
+    ($a:expr, $b:expr, $c:expr, $d:expr, $e:expr $(,)? => $f:expr) => {
+        $crate::validation::map5($a, $b, $c, $d, $e, $f)
+    };
+    ($a:expr, $b:expr, $c:expr, $d:expr, $e:expr, $g:expr $(,)? => $f:expr) => {
+        $crate::validation::map6($a, $b, $c, $d, $e, $g, $f)
+    };
+    ($a:expr, $b:expr, $c:expr, $d:expr, $e:expr, $g:expr, $h:expr $(,)? => $f:expr) => {
+        $crate::validation::map7($a, $b, $c, $d, $e, $g, $h, $f)
+    };
+    ($a:expr, $b:expr, $c:expr, $d:expr, $e:expr, $g:expr, $h:expr, $i:expr $(,)? => $f:expr) => {
+        $crate::validation::map8($a, $b, $c, $d, $e, $g, $h, $i, $f)
+    };
+}
+
+/// A list with at least one element, such as the errors of an invalid
+/// [`Validation`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NonEmpty<T> {
+    pub head: T,
+    pub tail: Vec<T>,
+}
+
+impl<T> NonEmpty<T> {
+    /// Creates a list holding only `head`.
+    pub fn new(head: T) -> Self {
+        NonEmpty {
+            head,
+            tail: Vec::new(),
+        }
+    }
+
+    /// Adds `value` at the end of the list.
+    pub fn push(&mut self, value: T) {
+        self.tail.push(value);
+    }
+
+    /// Returns the first element.
+    pub fn first(&self) -> &T {
+        &self.head
+    }
+
+    /// Iterates over references to the elements, from first to last.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        std::iter::once(&self.head).chain(&self.tail)
+    }
+
+    /// Applies `f` to every element.
+    pub fn map<U, F>(self, mut f: F) -> NonEmpty<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        NonEmpty {
+            head: f(self.head),
+            tail: self.tail.into_iter().map(f).collect(),
+        }
+    }
+
+    /// Returns the `NonEmpty` with the elements of `vec`, or `None` if it is
+    /// empty.
+    pub fn from_vec(mut vec: Vec<T>) -> Option<Self> {
+        if vec.is_empty() {
+            None
+        } else {
+            let head = vec.remove(0);
+            Some(NonEmpty { head, tail: vec })
+        }
+    }
+}
+
+impl<T> Semigroup for NonEmpty<T> {
+    fn combine(mut self, other: Self) -> Self {
+        self.tail.push(other.head);
+        self.tail.extend(other.tail);
+        self
+    }
+}
+
+impl<T> IntoIterator for NonEmpty<T> {
+    type Item = T;
+    type IntoIter = std::iter::Chain<std::iter::Once<T>, std::vec::IntoIter<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        std::iter::once(self.head).chain(self.tail)
+    }
+}
+
+impl<T> From<NonEmpty<T>> for Vec<T> {
+    fn from(list: NonEmpty<T>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+/// The result of a validation: either a valid value, or all the errors found.
+///
+/// `Validation` is a `Result` that accumulates errors: combining two invalid
+/// validations keeps the errors of both. It implements [`Applicative`] but not
+/// `Monad`, since chaining with [`and_then`](Validation::and_then) cannot
+/// look past the first invalid step.
+///
+/// A `Result<T, E>` converts into a `Validation<E, T>`, and a
+/// `Validation<E, T>` into a `Result<T, NonEmpty<E>>`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Validation<E, T> {
+    Valid(T),
+    Invalid(NonEmpty<E>),
+}
+
+impl<E, T> Validation<E, T> {
+    /// An invalid validation with the single error `error`.
+    pub fn invalid(error: E) -> Self {
+        Validation::Invalid(NonEmpty::new(error))
+    }
+
+    /// Returns `true` if the validation is valid.
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Validation::Valid(_))
+    }
+
+    /// Returns `true` if the validation is invalid.
+    pub fn is_invalid(&self) -> bool {
+        matches!(self, Validation::Invalid(_))
+    }
+
+    /// Applies `f` to a valid value.
+    pub fn map<U, F>(self, f: F) -> Validation<E, U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        match self {
+            Validation::Valid(x) => Validation::Valid(f(x)),
+            Validation::Invalid(errors) => Validation::Invalid(errors),
+        }
+    }
+
+    /// Applies `f` to every error.
+    pub fn map_err<E2, F>(self, f: F) -> Validation<E2, T>
+    where
+        F: FnMut(E) -> E2,
+    {
+        match self {
+            Validation::Valid(x) => Validation::Valid(x),
+            Validation::Invalid(errors) => Validation::Invalid(errors.map(f)),
+        }
+    }
+
+    /// Pairs two valid values, or collects the errors of both validations.
+    ///
+    /// ```rust
+    /// use functional_rs::Validation;
+    /// let a: Validation<&str, i32> = Validation::invalid("a");
+    /// let b: Validation<&str, i32> = Validation::invalid("b");
+    /// let errors: Vec<_> = a.zip(b).unwrap_invalid().into();
+    /// assert_eq!(errors, ["a", "b"]);
+    /// ```
+    pub fn zip<U>(self, other: Validation<E, U>) -> Validation<E, (T, U)> {
+        match (self, other) {
+            (Validation::Valid(x), Validation::Valid(y)) => Validation::Valid((x, y)),
+            (Validation::Invalid(a), Validation::Invalid(b)) => Validation::Invalid(a.combine(b)),
+            (Validation::Invalid(errors), _) | (_, Validation::Invalid(errors)) => {
+                Validation::Invalid(errors)
+            }
+        }
+    }
+
+    /// Validates a valid value further with `f`. Unlike with [`zip`], the
+    /// errors of `f` are only found when this validation is valid.
+    ///
+    /// [`zip`]: Validation::zip
+    pub fn and_then<U, F>(self, f: F) -> Validation<E, U>
+    where
+        F: FnOnce(T) -> Validation<E, U>,
+    {
+        match self {
+            Validation::Valid(x) => f(x),
+            Validation::Invalid(errors) => Validation::Invalid(errors),
+        }
+    }
+
+    /// Returns the errors of an invalid validation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the validation is valid.
+    pub fn unwrap_invalid(self) -> NonEmpty<E> {
+        match self {
+            Validation::Valid(_) => {
+                panic!("called `Validation::unwrap_invalid()` on a `Valid` value")
+            }
+            Validation::Invalid(errors) => errors,
+        }
+    }
+}
+
+impl<E, T> From<Result<T, E>> for Validation<E, T> {
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(x) => Validation::Valid(x),
+            Err(e) => Validation::invalid(e),
+        }
+    }
+}
+
+impl<E, T> From<Validation<E, T>> for Result<T, NonEmpty<E>> {
+    fn from(validation: Validation<E, T>) -> Self {
+        match validation {
+            Validation::Valid(x) => Ok(x),
+            Validation::Invalid(errors) => Err(errors),
+        }
+    }
+}
+
+impl<'a, E: 'a, T: 'a> Functor<'a> for Validation<E, T> {
+    type Inner = T;
+    type Wrapped<B: 'a> = Validation<E, B>;
+
+    fn fmap<B, F>(self, f: F) -> Validation<E, B>
+    where
+        B: 'a,
+        F: Fn(T) -> B + 'a,
+    {
+        self.map(f)
+    }
+}
+
+impl<'a, E: 'a, T: 'a> Applicative<'a> for Validation<E, T> {
+    fn pure(x: T) -> Self {
+        Validation::Valid(x)
+    }
+
+    fn zip<B>(self, other: Validation<E, B>) -> Validation<E, (T, B)>
+    where
+        B: Clone + 'a,
+    {
+        Validation::zip(self, other)
+    }
+}
+
+macro_rules! impl_map_n {
+    ($name:ident, $count:literal: $first:ident $First:ident $(, $arg:ident $Arg:ident)+) => {
+        #[doc = concat!("Applies `f` to the values of ", $count, " validations if all of them")]
+        /// are valid, or collects the errors of all the invalid ones.
+        #[allow(clippy::too_many_arguments)]
+        pub fn $name<E, $First, $($Arg,)+ R, F>(
+            $first: Validation<E, $First>,
+            $($arg: Validation<E, $Arg>,)+
+            f: F,
+        ) -> Validation<E, R>
+        where
+            F: FnOnce($First, $($Arg),+) -> R,
+        {
+            $first
+                $(.zip($arg))+
+                .map(|impl_map_n!(@pattern [$first] $($arg)+)| f($first, $($arg),+))
+        }
+    };
+    (@pattern [$($pattern:tt)+] $next:ident $($arg:ident)*) => {
+        impl_map_n!(@pattern [($($pattern)+, $next)] $($arg)*)
+    };
+    (@pattern [$($pattern:tt)+]) => {
+        $($pattern)+
+    };
+}
+
+impl_map_n!(map2, "two": v1 T1, v2 T2);
+impl_map_n!(map3, "three": v1 T1, v2 T2, v3 T3);
+impl_map_n!(map4, "four": v1 T1, v2 T2, v3 T3, v4 T4);
+impl_map_n!(map5, "five": v1 T1, v2 T2, v3 T3, v4 T4, v5 T5);
+impl_map_n!(map6, "six": v1 T1, v2 T2, v3 T3, v4 T4, v5 T5, v6 T6);
+impl_map_n!(map7, "seven": v1 T1, v2 T2, v3 T3, v4 T4, v5 T5, v6 T6, v7 T7);
+impl_map_n!(map8, "eight": v1 T1, v2 T2, v3 T3, v4 T4, v5 T5, v6 T6, v7 T7, v8 T8);
+
+/// `ValidFn` is a function wrapper for validators, returning a
+/// `Validation<E, U>`.
+///
+/// Composing with `>>` is Kleisli composition, as for [`TryFn`]: the next
+/// stage validates the valid value further, and the pipeline stops at the
+/// first invalid stage. Errors of later stages are converted into `E` with
+/// `From`. `&` runs two validators on the same input and collects the errors
+/// of both, and [`check`](ValidFn::check) turns a predicate into a
+/// validator.
+///
+/// [`TryFn`]: crate::TryFn
+///
+/// ```rust
+/// use functional_rs::ValidFn;
+///
+/// let password = ValidFn::<_, _, &str>::lift(str::trim)
+///     >> (ValidFn::check(|s: &&str| s.len() >= 8, "too short")
+///         & ValidFn::check(|s: &&str| s.chars().any(|c| c.is_ascii_digit()), "no digit"))
+///     .map(|(s, _): (&str, &str)| s.to_string());
+///
+/// assert!(password(" secret12 ").is_valid());
+/// let errors: Vec<_> = password("abc").unwrap_invalid().into();
+/// assert_eq!(errors, ["too short", "no digit"]);
+/// ```
+pub struct ValidFn<'a, T, U, E>(pub Box<dyn Fn(T) -> Validation<E, U> + 'a>);
+
+impl_unary_fn!(ValidFn<U, E> => Validation<E, U>);
+
+impl<'a, T, U, E> ValidFn<'a, T, U, E> {
+    /// Creates a `ValidFn` from a stage that cannot fail.
+    pub fn lift<F>(f: F) -> Self
+    where
+        F: Apply<(T,), Output = U> + 'a,
+    {
+        ValidFn(Box::new(move |x| Validation::Valid(f.apply((x,)))))
+    }
+
+    /// Adds a stage that cannot fail, applied to the valid value.
+    pub fn map<G>(self, g: G) -> ValidFn<'a, T, G::Output, E>
+    where
+        T: 'a,
+        U: 'a,
+        E: 'a,
+        G: Apply<(U,)> + 'a,
+    {
+        ValidFn(Box::new(move |x| (self.0)(x).map(|y| g.apply((y,)))))
+    }
+
+    /// Transforms every error of the pipeline with `g`.
+    pub fn map_err<E2, G>(self, g: G) -> ValidFn<'a, T, U, E2>
+    where
+        T: 'a,
+        U: 'a,
+        E: 'a,
+        G: Apply<(E,), Output = E2> + 'a,
+    {
+        ValidFn(Box::new(move |x| (self.0)(x).map_err(|e| g.apply((e,)))))
+    }
+}
+
+impl<'a, T, E> ValidFn<'a, T, T, E> {
+    /// A validator passing its input through if `pred` holds for it, and
+    /// failing with a clone of `error` otherwise.
+    pub fn check<P>(pred: P, error: E) -> Self
+    where
+        E: Clone + 'a,
+        P: Fn(&T) -> bool + 'a,
+    {
+        ValidFn(Box::new(move |x| {
+            if pred(&x) {
+                Validation::Valid(x)
+            } else {
+                Validation::invalid(error.clone())
+            }
+        }))
+    }
+}
+
+impl<'a, T, U, E, G, V, E2> std::ops::Shr<G> for ValidFn<'a, T, U, E>
+where
+    T: 'a,
+    U: 'a,
+    E: From<E2> + 'a,
+    G: Apply<(U,), Output = Validation<E2, V>> + 'a,
+{
+    type Output = ValidFn<'a, T, V, E>;
+
+    fn shr(self, rhs: G) -> Self::Output {
+        ValidFn(Box::new(move |x: T| {
+            (self.0)(x).and_then(|y| rhs.apply((y,)).map_err(E::from))
+        }))
+    }
+}
+
+impl<'a, T, U, E, G, V> std::ops::BitAnd<G> for ValidFn<'a, T, U, E>
+where
+    T: Clone + 'a,
+    U: 'a,
+    E: 'a,
+    G: Apply<(T,), Output = Validation<E, V>> + 'a,
+{
+    type Output = ValidFn<'a, T, (U, V), E>;
+
+    fn bitand(self, rhs: G) -> Self::Output {
+        ValidFn(Box::new(move |x: T| {
+            (self.0)(x.clone()).zip(rhs.apply((x,)))
+        }))
+    }
+}
+
+impl<'a, T, U, E> From<ComposableFn<'a, T, Validation<E, U>>> for ValidFn<'a, T, U, E> {
+    fn from(f: ComposableFn<'a, T, Validation<E, U>>) -> Self {
+        ValidFn(f.0)
+    }
+}
+
+impl<'a, T, U, E> From<ValidFn<'a, T, U, E>> for ComposableFn<'a, T, Validation<E, U>> {
+    fn from(f: ValidFn<'a, T, U, E>) -> Self {
+        ComposableFn(f.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::f;
+    use crate::monad::traverse;
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum FieldError {
+        Empty(&'static str),
+        TooLong(&'static str),
+        NotANumber(&'static str),
+        OutOfRange(&'static str),
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Signup {
+        name: String,
+        email: String,
+        age: u8,
+    }
+
+    fn text<'a>(field: &'static str, max: usize) -> ValidFn<'a, &'a str, String, FieldError> {
+        (ValidFn::lift(str::trim)
+            >> ValidFn::check(|s: &&str| !s.is_empty(), FieldError::Empty(field)))
+        .map(f!(|s: &str| s.to_string()))
+            >> move |s: String| {
+                if s.len() > max {
+                    Validation::invalid(FieldError::TooLong(field))
+                } else {
+                    Validation::Valid(s)
+                }
+            }
+    }
+
+    fn age<'a>() -> ValidFn<'a, &'a str, u8, FieldError> {
+        valid_f!(
+            |s: &str| Validation::from(s.parse::<i64>()).map_err(|_| FieldError::NotANumber("age"))
+        ) >> |n: i64| Validation::from(u8::try_from(n).map_err(|_| FieldError::OutOfRange("age")))
+    }
+
+    fn signup(name: &str, email: &str, age_text: &str) -> Validation<FieldError, Signup> {
+        validate!(Signup {
+            name: text("name", 10)(name),
+            email: text("email", 20)(email),
+            age: age()(age_text),
+        })
+    }
+
+    #[test]
+    fn test_validate_collects_every_error() {
+        assert_eq!(
+            signup(" ada ", "ada@example.com", "36"),
+            Validation::Valid(Signup {
+                name: "ada".to_string(),
+                email: "ada@example.com".to_string(),
+                age: 36,
+            })
+        );
+        let errors: Vec<_> = signup("  ", "ada.lovelace@example.com", "-1")
+            .unwrap_invalid()
+            .into();
+        assert_eq!(
+            errors,
+            [
+                FieldError::Empty("name"),
+                FieldError::TooLong("email"),
+                FieldError::OutOfRange("age"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_map_n() {
+        let valid = |x: i32| Validation::<&str, i32>::Valid(x);
+        let sum = map8(
+            valid(1),
+            valid(2),
+            valid(3),
+            valid(4),
+            valid(5),
+            valid(6),
+            valid(7),
+            valid(8),
+            |a, b, c, d, e, f, g, h| a + b + c + d + e + f + g + h,
+        );
+        let invalid = map3(
+            Validation::invalid("a"),
+            valid(2),
+            Validation::<_, i32>::invalid("c"),
+            |a: i32, b, c| a + b + c,
+        );
+
+        assert_eq!(sum, Validation::Valid(36));
+        assert_eq!(Vec::from(invalid.unwrap_invalid()), ["a", "c"]);
+        assert_eq!(
+            validate!(valid(1), valid(2) => |a, b| a * b),
+            Validation::Valid(2)
+        );
+    }
+
+    #[test]
+    fn test_and_then_stops_at_first_error() {
+        let validation: Validation<&str, i32> = Validation::invalid("first");
+        let chained = validation.and_then(|_| Validation::<_, i32>::invalid("second"));
+
+        assert_eq!(Vec::from(chained.unwrap_invalid()), ["first"]);
+        assert_eq!(
+            Validation::<&str, _>::Valid(2).and_then(|x| Validation::Valid(x * 2)),
+            Validation::Valid(4)
+        );
+    }
+
+    #[test]
+    fn test_result_conversions() {
+        let ok: Result<i32, &str> = Ok(1);
+        let err: Result<i32, &str> = Err("bad");
+
+        assert_eq!(Validation::from(ok), Validation::Valid(1));
+        assert_eq!(Result::from(Validation::from(ok)), Ok(1));
+        assert_eq!(
+            Result::from(Validation::from(err)),
+            Err(NonEmpty::new("bad"))
+        );
+    }
+
+    #[test]
+    fn test_traverse_accumulates() {
+        let parse = |s: &str| Validation::from(s.parse::<i32>().map_err(|_| s.to_string()));
+
+        assert_eq!(traverse(["1", "2"], parse), Validation::Valid(vec![1, 2]));
+        assert_eq!(
+            Vec::from(traverse(["x", "2", "y"], parse).unwrap_invalid()),
+            ["x", "y"]
+        );
+    }
+
+    #[test]
+    fn test_non_empty() {
+        let mut list = NonEmpty::new(1);
+        list.push(2);
+        let combined = list.clone().combine(NonEmpty::new(3)).map(|x| x * 10);
+
+        assert_eq!(*list.first(), 1);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(Vec::from(combined), [10, 20, 30]);
+        assert_eq!(
+            NonEmpty::from_vec(vec![4, 5]).map(Vec::from),
+            Some(vec![4, 5])
+        );
+        assert_eq!(NonEmpty::<i32>::from_vec(Vec::new()), None);
+    }
+}
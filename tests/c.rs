@@ -0,0 +1,5 @@
+#[test]
+fn c_compile_errors() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/c/*.rs");
+}
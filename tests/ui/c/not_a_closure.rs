@@ -0,0 +1,9 @@
+use functional_rs::c;
+
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+fn main() {
+    let _ = c!(add);
+}
@@ -0,0 +1,5 @@
+use functional_rs::c;
+
+fn main() {
+    let _ = c!(|a: i32, b: i32| a + b, 1);
+}
@@ -0,0 +1,8 @@
+use functional_rs::curry;
+
+#[curry(name = "add_curried")]
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+fn main() {}
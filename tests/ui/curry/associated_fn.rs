@@ -0,0 +1,12 @@
+use functional_rs::curry;
+
+struct Point(i32, i32);
+
+impl Point {
+    #[curry]
+    fn new(x: i32, y: i32) -> Self {
+        Point(x, y)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use functional_rs::curry;
+
+struct Token;
+
+#[curry]
+fn consume(token: Token, n: i32) -> i32 {
+    let _ = token;
+    n
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use functional_rs::curry;
+
+struct Counter(i32);
+
+impl Counter {
+    #[curry]
+    fn add(&self, a: i32, b: i32) -> i32 {
+        self.0 + a + b
+    }
+}
+
+fn main() {}
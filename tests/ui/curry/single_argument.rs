@@ -0,0 +1,8 @@
+use functional_rs::curry;
+
+#[curry]
+fn double(a: i32) -> i32 {
+    a * 2
+}
+
+fn main() {}
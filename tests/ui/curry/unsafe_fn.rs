@@ -0,0 +1,8 @@
+use functional_rs::curry;
+
+#[curry]
+unsafe fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+fn main() {}
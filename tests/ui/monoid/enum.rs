@@ -0,0 +1,9 @@
+use functional_rs::Semigroup;
+
+#[derive(Semigroup)]
+enum Total {
+    Count(u32),
+    Empty,
+}
+
+fn main() {}
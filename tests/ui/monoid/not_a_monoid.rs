@@ -0,0 +1,10 @@
+use functional_rs::monoid::Sum;
+use functional_rs::{Monoid, Semigroup};
+
+#[derive(Semigroup, Monoid)]
+struct Totals {
+    count: Sum<u32>,
+    average: f64,
+}
+
+fn main() {}
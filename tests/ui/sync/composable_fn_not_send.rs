@@ -0,0 +1,7 @@
+use functional_rs::ComposableFn;
+
+fn require_send<T: Send>() {}
+
+fn main() {
+    require_send::<ComposableFn<i32, i32>>();
+}
@@ -0,0 +1,9 @@
+use functional_rs::{f_send, SendFn};
+use std::rc::Rc;
+
+fn main() {
+    let offset = Rc::new(10);
+    let add_offset = move |x: i32| x + *offset;
+    let start: SendFn<i32, i32> = f_send!(|x: i32| x + 1);
+    let _pipeline = std::ops::Shr::shr(start, add_offset);
+}